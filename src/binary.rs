@@ -1,6 +1,8 @@
 //! Chapter 3. Binary Encoding.
 
-use core::marker::PhantomData;
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::{marker::PhantomData, ops::Range};
 
 /// SBI functions return type.
 ///
@@ -41,6 +43,16 @@ pub const RET_ERR_ALREADY_STARTED: usize = -7isize as _;
 pub const RET_ERR_ALREADY_STOPPED: usize = -8isize as _;
 /// Error for shared memory not available.
 pub const RET_ERR_NO_SHMEM: usize = -9isize as _;
+/// Error for invalid state.
+pub const RET_ERR_INVALID_STATE: usize = -10isize as _;
+/// Error for bad or invalid range.
+pub const RET_ERR_BAD_RANGE: usize = -11isize as _;
+/// Error for failed due to timeout.
+pub const RET_ERR_TIMEOUT: usize = -12isize as _;
+/// Error for input or output error.
+pub const RET_ERR_IO: usize = -13isize as _;
+/// Error for denied or not allowed due to lock status.
+pub const RET_ERR_DENIED_LOCKED: usize = -14isize as _;
 
 impl core::fmt::Debug for SbiRet {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -55,6 +67,11 @@ impl core::fmt::Debug for SbiRet {
             RET_ERR_ALREADY_STARTED => write!(f, "<SBI already started>"),
             RET_ERR_ALREADY_STOPPED => write!(f, "<SBI already stopped>"),
             RET_ERR_NO_SHMEM => write!(f, "<SBI shared memory not available>"),
+            RET_ERR_INVALID_STATE => write!(f, "<SBI invalid state>"),
+            RET_ERR_BAD_RANGE => write!(f, "<SBI bad range>"),
+            RET_ERR_TIMEOUT => write!(f, "<SBI timeout>"),
+            RET_ERR_IO => write!(f, "<SBI input/output error>"),
+            RET_ERR_DENIED_LOCKED => write!(f, "<SBI denied due to locked status>"),
             unknown => write!(f, "[SBI Unknown error: {unknown:#x}]"),
         }
     }
@@ -81,10 +98,45 @@ pub enum Error {
     AlreadyStopped,
     /// Error for shared memory not available.
     NoShmem,
+    /// Error for invalid state.
+    InvalidState,
+    /// Error for bad or invalid range.
+    BadRange,
+    /// Error for failed due to timeout.
+    Timeout,
+    /// Error for input or output error.
+    Io,
+    /// Error for denied or not allowed due to lock status.
+    DeniedLocked,
     /// Custom error code.
     Custom(isize),
 }
 
+impl From<Error> for SbiRet {
+    /// Converts an SBI error into `SbiRet` with the corresponding error code.
+    #[inline]
+    fn from(error: Error) -> Self {
+        let error = match error {
+            Error::Failed => RET_ERR_FAILED,
+            Error::NotSupported => RET_ERR_NOT_SUPPORTED,
+            Error::InvalidParam => RET_ERR_INVALID_PARAM,
+            Error::Denied => RET_ERR_DENIED,
+            Error::InvalidAddress => RET_ERR_INVALID_ADDRESS,
+            Error::AlreadyAvailable => RET_ERR_ALREADY_AVAILABLE,
+            Error::AlreadyStarted => RET_ERR_ALREADY_STARTED,
+            Error::AlreadyStopped => RET_ERR_ALREADY_STOPPED,
+            Error::NoShmem => RET_ERR_NO_SHMEM,
+            Error::InvalidState => RET_ERR_INVALID_STATE,
+            Error::BadRange => RET_ERR_BAD_RANGE,
+            Error::Timeout => RET_ERR_TIMEOUT,
+            Error::Io => RET_ERR_IO,
+            Error::DeniedLocked => RET_ERR_DENIED_LOCKED,
+            Error::Custom(code) => code as usize,
+        };
+        Self { error, value: 0 }
+    }
+}
+
 impl SbiRet {
     /// Returns success SBI state with given `value`.
     #[inline]
@@ -190,6 +242,55 @@ impl SbiRet {
             value: 0,
         }
     }
+
+    /// SBI call failed for the target resource is in an invalid state,
+    /// e.g. a software event is not in the state expected by the function.
+    #[inline]
+    pub const fn invalid_state() -> Self {
+        Self {
+            error: RET_ERR_INVALID_STATE,
+            value: 0,
+        }
+    }
+
+    /// SBI call failed for a bad or invalid range,
+    /// e.g. message data exceeds the size of shared memory.
+    #[inline]
+    pub const fn bad_range() -> Self {
+        Self {
+            error: RET_ERR_BAD_RANGE,
+            value: 0,
+        }
+    }
+
+    /// SBI call failed for timeout,
+    /// e.g. a message proxy request is not completed in time.
+    #[inline]
+    pub const fn timeout() -> Self {
+        Self {
+            error: RET_ERR_TIMEOUT,
+            value: 0,
+        }
+    }
+
+    /// SBI call failed for an input or output error.
+    #[inline]
+    pub const fn io() -> Self {
+        Self {
+            error: RET_ERR_IO,
+            value: 0,
+        }
+    }
+
+    /// SBI call failed for the target resource is locked,
+    /// e.g. a firmware feature cannot be changed after being locked.
+    #[inline]
+    pub const fn denied_locked() -> Self {
+        Self {
+            error: RET_ERR_DENIED_LOCKED,
+            value: 0,
+        }
+    }
 }
 
 impl SbiRet {
@@ -207,6 +308,11 @@ impl SbiRet {
             RET_ERR_ALREADY_STARTED => Err(Error::AlreadyStarted),
             RET_ERR_ALREADY_STOPPED => Err(Error::AlreadyStopped),
             RET_ERR_NO_SHMEM => Err(Error::NoShmem),
+            RET_ERR_INVALID_STATE => Err(Error::InvalidState),
+            RET_ERR_BAD_RANGE => Err(Error::BadRange),
+            RET_ERR_TIMEOUT => Err(Error::Timeout),
+            RET_ERR_IO => Err(Error::Io),
+            RET_ERR_DENIED_LOCKED => Err(Error::DeniedLocked),
             unknown => Err(Error::Custom(unknown as _)),
         }
     }
@@ -307,21 +413,19 @@ impl SbiRet {
     /// Gets detail of a PMU counter and judge if it is a firmware counter.
     ///
     /// ```
-    /// # use sbi_spec::binary::SbiRet;
-    /// # use core::mem::size_of;
+    /// # use sbi_spec::{binary::SbiRet, pmu::CounterInfo};
     /// # mod sbi_rt {
-    /// #     use sbi_spec::binary::SbiRet;
-    /// #     const TYPE_MASK: usize = 1 << (core::mem::size_of::<usize>() - 1);
-    /// #     pub fn pmu_counter_get_info(_: usize) -> SbiRet { SbiRet::success(TYPE_MASK) }
+    /// #     use sbi_spec::{binary::SbiRet, pmu::CounterInfo};
+    /// #     pub fn pmu_counter_get_info(_: usize) -> SbiRet {
+    /// #         SbiRet::success(CounterInfo::firmware().raw())
+    /// #     }
     /// # }
     /// // We assume that counter index 42 is a firmware counter.
     /// let counter_idx = 42;
-    /// // Masks PMU counter type by setting highest bit in `usize`.
-    /// const TYPE_MASK: usize = 1 << (size_of::<usize>() - 1);
     /// // Highest bit of returned `counter_info` represents whether it's
     /// // a firmware counter or a hardware counter.
     /// let is_firmware_counter = sbi_rt::pmu_counter_get_info(counter_idx)
-    ///     .map(|counter_info| counter_info & TYPE_MASK != 0);
+    ///     .map(|counter_info| CounterInfo::from_raw(counter_info).is_firmware());
     /// // If that bit is set, it is a firmware counter.
     /// assert_eq!(is_firmware_counter, Ok(true));
     /// ```
@@ -673,7 +777,1974 @@ impl SbiRet {
     }
 }
 
+/// Standard SBI function call decoded from `ecall` registers.
+///
+/// > An ECALL is used as the control transfer instruction between the supervisor
+/// > and the SEE. a7 encodes the SBI extension ID (EID), a6 encodes the SBI
+/// > function ID (FID) for a given extension ID encoded in a7 for any SBI
+/// > extension defined in or after SBI v0.2.
+///
+/// Each variant names one standard SBI function, carrying its parameters as declared
+/// by the specification. Parameters of type `uint64_t` are combined from two registers
+/// on RV32 platforms and are read from one register on wider platforms.
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::{binary::{SbiCall, SbiRet}, hsm};
+/// let regs = [0x3, 0x8020_0000, 0x1234, 0, 0, 0, hsm::HART_START, hsm::EID_HSM];
+/// let ret = match SbiCall::decode(regs) {
+///     Ok(SbiCall::HsmHartStart { hartid, start_addr, opaque }) => {
+///         assert_eq!((hartid, start_addr, opaque), (0x3, 0x8020_0000, 0x1234));
+///         SbiRet::success(0)
+///     }
+///     // Other calls, and calls with unknown extension or function ID.
+///     Ok(_) | Err(_) => SbiRet::not_supported(),
+/// };
+/// assert_eq!(ret, SbiRet::success(0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SbiCall {
+    /// Legacy function to program the clock for next event after `stime_value` time.
+    ///
+    /// Declared in §5.1.
+    #[cfg(feature = "legacy")]
+    LegacySetTimer {
+        /// Absolute time of next timer event.
+        stime_value: u64,
+    },
+    /// Legacy function to write a character to the debug console.
+    ///
+    /// Declared in §5.2.
+    #[cfg(feature = "legacy")]
+    LegacyConsolePutchar {
+        /// Character to write.
+        ch: usize,
+    },
+    /// Legacy function to read a character from the debug console.
+    ///
+    /// Declared in §5.3.
+    #[cfg(feature = "legacy")]
+    LegacyConsoleGetchar,
+    /// Legacy function to clear pending IPIs on the calling hart.
+    ///
+    /// Declared in §5.4.
+    #[cfg(feature = "legacy")]
+    LegacyClearIpi,
+    /// Legacy function to send an inter-processor interrupt to harts in hart mask.
+    ///
+    /// Declared in §5.5.
+    #[cfg(feature = "legacy")]
+    LegacySendIpi {
+        /// Virtual address of the hart mask.
+        hart_mask: usize,
+    },
+    /// Legacy function to execute `FENCE.I` on remote harts.
+    ///
+    /// Declared in §5.6.
+    #[cfg(feature = "legacy")]
+    LegacyRemoteFenceI {
+        /// Virtual address of the hart mask.
+        hart_mask: usize,
+    },
+    /// Legacy function to execute `SFENCE.VMA` on remote harts.
+    ///
+    /// Declared in §5.7.
+    #[cfg(feature = "legacy")]
+    LegacyRemoteSfenceVma {
+        /// Virtual address of the hart mask.
+        hart_mask: usize,
+        /// Start of the virtual address range.
+        start: usize,
+        /// Size of the virtual address range.
+        size: usize,
+    },
+    /// Legacy function to execute `SFENCE.VMA` with address space ID on remote harts.
+    ///
+    /// Declared in §5.8.
+    #[cfg(feature = "legacy")]
+    LegacyRemoteSfenceVmaAsid {
+        /// Virtual address of the hart mask.
+        hart_mask: usize,
+        /// Start of the virtual address range.
+        start: usize,
+        /// Size of the virtual address range.
+        size: usize,
+        /// Address space ID.
+        asid: usize,
+    },
+    /// Legacy function to put all harts to shutdown state.
+    ///
+    /// Declared in §5.9.
+    #[cfg(feature = "legacy")]
+    LegacyShutdown,
+    /// Get the current SBI specification version.
+    ///
+    /// Declared in §4.1.
+    BaseGetSpecVersion,
+    /// Get the current SBI implementation ID.
+    ///
+    /// Declared in §4.2.
+    BaseGetImplId,
+    /// Get the current SBI implementation version.
+    ///
+    /// Declared in §4.3.
+    BaseGetImplVersion,
+    /// Probe information about one SBI extension.
+    ///
+    /// Declared in §4.4.
+    BaseProbeExtension {
+        /// Extension ID to probe.
+        extension_id: usize,
+    },
+    /// Get value of `mvendorid` register.
+    ///
+    /// Declared in §4.5.
+    BaseGetMvendorid,
+    /// Get value of `marchid` register.
+    ///
+    /// Declared in §4.6.
+    BaseGetMarchid,
+    /// Get value of `mimpid` register.
+    ///
+    /// Declared in §4.7.
+    BaseGetMimpid,
+    /// Program the clock for next event after an absolute time.
+    ///
+    /// Declared in §6.1.
+    TimeSetTimer {
+        /// Absolute time of next timer event.
+        stime_value: u64,
+    },
+    /// Send an inter-processor interrupt to all harts defined in hart mask.
+    ///
+    /// Declared in §7.1.
+    SpiSendIpi {
+        /// Harts to send the interrupt to.
+        hart_mask: HartMask,
+    },
+    /// Execute `FENCE.I` instruction on remote harts.
+    ///
+    /// Declared in §8.1.
+    RfncRemoteFenceI {
+        /// Remote harts to execute the fence.
+        hart_mask: HartMask,
+    },
+    /// Execute `SFENCE.VMA` for all address spaces on remote harts.
+    ///
+    /// Declared in §8.2.
+    RfncRemoteSfenceVma {
+        /// Remote harts to execute the fence.
+        hart_mask: HartMask,
+        /// Start of the virtual address range.
+        start: usize,
+        /// Size of the virtual address range.
+        size: usize,
+    },
+    /// Execute address space based `SFENCE.VMA` on remote harts.
+    ///
+    /// Declared in §8.3.
+    RfncRemoteSfenceVmaAsid {
+        /// Remote harts to execute the fence.
+        hart_mask: HartMask,
+        /// Start of the virtual address range.
+        start: usize,
+        /// Size of the virtual address range.
+        size: usize,
+        /// Address space ID.
+        asid: usize,
+    },
+    /// Execute virtual machine ID based `HFENCE.GVMA` on remote harts.
+    ///
+    /// Declared in §8.4.
+    RfncRemoteHfenceGvmaVmid {
+        /// Remote harts to execute the fence.
+        hart_mask: HartMask,
+        /// Start of the guest physical address range.
+        start: usize,
+        /// Size of the guest physical address range.
+        size: usize,
+        /// Virtual machine ID.
+        vmid: usize,
+    },
+    /// Execute `HFENCE.GVMA` for all virtual machines on remote harts.
+    ///
+    /// Declared in §8.5.
+    RfncRemoteHfenceGvma {
+        /// Remote harts to execute the fence.
+        hart_mask: HartMask,
+        /// Start of the guest physical address range.
+        start: usize,
+        /// Size of the guest physical address range.
+        size: usize,
+    },
+    /// Execute address space based `HFENCE.VVMA` for current virtual machine on remote harts.
+    ///
+    /// Declared in §8.6.
+    RfncRemoteHfenceVvmaAsid {
+        /// Remote harts to execute the fence.
+        hart_mask: HartMask,
+        /// Start of the guest virtual address range.
+        start: usize,
+        /// Size of the guest virtual address range.
+        size: usize,
+        /// Address space ID.
+        asid: usize,
+    },
+    /// Execute `HFENCE.VVMA` for all address spaces in current virtual machine on remote harts.
+    ///
+    /// Declared in §8.7.
+    RfncRemoteHfenceVvma {
+        /// Remote harts to execute the fence.
+        hart_mask: HartMask,
+        /// Start of the guest virtual address range.
+        start: usize,
+        /// Size of the guest virtual address range.
+        size: usize,
+    },
+    /// Start executing the given hart at specified address in supervisor-mode.
+    ///
+    /// Declared in §9.1.
+    HsmHartStart {
+        /// Hart ID of the target hart.
+        hartid: usize,
+        /// Physical address where the hart starts executing.
+        start_addr: usize,
+        /// Value passed to the started hart in `a1` register.
+        opaque: usize,
+    },
+    /// Stop executing the calling hart in supervisor-mode.
+    ///
+    /// Declared in §9.2.
+    HsmHartStop,
+    /// Get the current status (or HSM state id) of the given hart.
+    ///
+    /// Declared in §9.3.
+    HsmHartGetStatus {
+        /// Hart ID of the target hart.
+        hartid: usize,
+    },
+    /// Put the calling hart into suspend or platform specific lower power states.
+    ///
+    /// Declared in §9.4.
+    HsmHartSuspend {
+        /// Suspend type.
+        suspend_type: crate::hsm::SuspendType,
+        /// Physical address where the hart resumes for non-retentive suspend.
+        resume_addr: usize,
+        /// Value passed to the resumed hart in `a1` register.
+        opaque: usize,
+    },
+    /// Reset the system based on provided reset type and reason.
+    ///
+    /// Declared in §10.1.
+    SrstSystemReset {
+        /// Reset type.
+        reset_type: crate::srst::ResetType,
+        /// Reset reason.
+        reset_reason: crate::srst::ResetReason,
+    },
+    /// Get the number of counters, both hardware and firmware.
+    ///
+    /// Declared in §11.5.
+    PmuNumCounters,
+    /// Get details about the specified counter.
+    ///
+    /// Declared in §11.6.
+    PmuCounterGetInfo {
+        /// Index of the counter.
+        counter_idx: usize,
+    },
+    /// Find and configure a counter from a set of counters.
+    ///
+    /// Declared in §11.7.
+    PmuCounterConfigMatching {
+        /// Set of counters.
+        counter_mask: crate::pmu::CounterMask,
+        /// Counter configuration flags.
+        config_flags: crate::pmu::ConfigFlags,
+        /// Event index to be monitored.
+        event_idx: usize,
+        /// Additional event configuration data.
+        event_data: u64,
+    },
+    /// Start or enable a set of counters on the calling hart with the specified initial value.
+    ///
+    /// Declared in §11.8.
+    PmuCounterStart {
+        /// Set of counters.
+        counter_mask: crate::pmu::CounterMask,
+        /// Counter start flags.
+        start_flags: crate::pmu::StartFlags,
+        /// Initial value of the counters.
+        initial_value: u64,
+    },
+    /// Stop or disable a set of counters on the calling hart.
+    ///
+    /// Declared in §11.9.
+    PmuCounterStop {
+        /// Set of counters.
+        counter_mask: crate::pmu::CounterMask,
+        /// Counter stop flags.
+        stop_flags: crate::pmu::StopFlags,
+    },
+    /// Provide the current value of a firmware counter.
+    ///
+    /// Declared in §11.10.
+    PmuCounterFwRead {
+        /// Index of the firmware counter.
+        counter_idx: usize,
+    },
+    /// Provide the upper 32 bits of the current firmware counter value.
+    ///
+    /// Declared in §11.11.
+    PmuCounterFwReadHi {
+        /// Index of the firmware counter.
+        counter_idx: usize,
+    },
+    /// Set and enable the PMU snapshot shared memory.
+    ///
+    /// Declared in §11.12.
+    PmuSnapshotSetShmem {
+        /// Low part of the shared memory physical address.
+        shmem_phys_lo: usize,
+        /// High part of the shared memory physical address.
+        shmem_phys_hi: usize,
+        /// Flags, reserved for future use.
+        flags: usize,
+    },
+    /// Write bytes to the debug console from input memory.
+    ///
+    /// Declared in §12.1.
+    DbcnConsoleWrite {
+        /// Number of bytes in input memory.
+        num_bytes: usize,
+        /// Low part of the input memory physical base address.
+        base_addr_lo: usize,
+        /// High part of the input memory physical base address.
+        base_addr_hi: usize,
+    },
+    /// Read bytes from the debug console into an output memory.
+    ///
+    /// Declared in §12.2.
+    DbcnConsoleRead {
+        /// Maximum number of bytes to be written into output memory.
+        num_bytes: usize,
+        /// Low part of the output memory physical base address.
+        base_addr_lo: usize,
+        /// High part of the output memory physical base address.
+        base_addr_hi: usize,
+    },
+    /// Write a single byte to the debug console.
+    ///
+    /// Declared in §12.3.
+    DbcnConsoleWriteByte {
+        /// Byte to write.
+        byte: u8,
+    },
+    /// Suspend under system-level sleep states.
+    ///
+    /// Declared in §13.1.
+    SuspSuspend {
+        /// Sleep type.
+        sleep_type: crate::susp::SleepType,
+        /// Physical address where harts resume after system suspend.
+        resume_addr: usize,
+        /// Value passed to the resumed hart in `a1` register.
+        opaque: usize,
+    },
+    /// Probe a CPPC register.
+    ///
+    /// Declared in §14.1.
+    CppcProbe {
+        /// CPPC register.
+        reg_id: crate::cppc::CppcReg,
+    },
+    /// Read CPPC register bits.
+    ///
+    /// Declared in §14.2.
+    CppcRead {
+        /// CPPC register.
+        reg_id: crate::cppc::CppcReg,
+    },
+    /// Read high bits of a CPPC register.
+    ///
+    /// Declared in §14.3.
+    CppcReadHi {
+        /// CPPC register.
+        reg_id: crate::cppc::CppcReg,
+    },
+    /// Write to a CPPC register.
+    ///
+    /// Declared in §14.4.
+    CppcWrite {
+        /// CPPC register.
+        reg_id: crate::cppc::CppcReg,
+        /// Value to write.
+        value: u64,
+    },
+    /// Probe a nested acceleration feature.
+    ///
+    /// Declared in §15.5.
+    NaclProbeFeature {
+        /// Nested acceleration feature ID.
+        feature_id: u32,
+    },
+    /// Set and enable the shared memory for nested acceleration on the calling hart.
+    ///
+    /// Declared in §15.6.
+    NaclSetShmem {
+        /// Low part of the shared memory physical address.
+        shmem_phys_lo: usize,
+        /// High part of the shared memory physical address.
+        shmem_phys_hi: usize,
+        /// Flags, reserved for future use.
+        flags: usize,
+    },
+    /// Synchronize CSRs in the nested acceleration shared memory.
+    ///
+    /// Declared in §15.7.
+    NaclSyncCsr {
+        /// CSR number to synchronize, or all ones for all CSRs.
+        csr_num: usize,
+    },
+    /// Synchronize HFENCEs in the nested acceleration shared memory.
+    ///
+    /// Declared in §15.8.
+    NaclSyncHfence {
+        /// HFENCE entry index to synchronize, or all ones for all entries.
+        entry_index: usize,
+    },
+    /// Synchronize CSRs and HFENCEs in the nested acceleration shared memory and emulate the SRET instruction.
+    ///
+    /// Declared in §15.9.
+    NaclSyncSret,
+    /// Set the shared memory physical base address for steal-time accounting of the calling virtual hart.
+    ///
+    /// Declared in §16.1.
+    StaSetShmem {
+        /// Low part of the shared memory physical address.
+        shmem_phys_lo: usize,
+        /// High part of the shared memory physical address.
+        shmem_phys_hi: usize,
+        /// Flags, reserved for future use.
+        flags: usize,
+    },
+    /// Read a range of event attribute values from a software event.
+    ///
+    /// Declared in §17.8.
+    SseReadAttrs {
+        /// Software event ID.
+        event_id: u32,
+        /// First attribute ID to read.
+        base_attr_id: u32,
+        /// Number of attributes to read.
+        attr_count: usize,
+        /// Low part of the output memory physical address.
+        output_phys_lo: usize,
+        /// High part of the output memory physical address.
+        output_phys_hi: usize,
+    },
+    /// Write a range of event attribute values to a software event.
+    ///
+    /// Declared in §17.9.
+    SseWriteAttrs {
+        /// Software event ID.
+        event_id: u32,
+        /// First attribute ID to write.
+        base_attr_id: u32,
+        /// Number of attributes to write.
+        attr_count: usize,
+        /// Low part of the input memory physical address.
+        input_phys_lo: usize,
+        /// High part of the input memory physical address.
+        input_phys_hi: usize,
+    },
+    /// Register a software event.
+    ///
+    /// Declared in §17.10.
+    SseRegister {
+        /// Software event ID.
+        event_id: u32,
+        /// Entry program counter of the event handler.
+        handler_entry_pc: usize,
+        /// Entry argument of the event handler.
+        handler_entry_arg: usize,
+    },
+    /// Unregister a software event.
+    ///
+    /// Declared in §17.11.
+    SseUnregister {
+        /// Software event ID.
+        event_id: u32,
+    },
+    /// Enable a software event.
+    ///
+    /// Declared in §17.12.
+    SseEnable {
+        /// Software event ID.
+        event_id: u32,
+    },
+    /// Disable a software event.
+    ///
+    /// Declared in §17.13.
+    SseDisable {
+        /// Software event ID.
+        event_id: u32,
+    },
+    /// Complete the supervisor event handling.
+    ///
+    /// Declared in §17.14.
+    SseComplete,
+    /// Inject a software event.
+    ///
+    /// Declared in §17.15.
+    SseInject {
+        /// Software event ID.
+        event_id: u32,
+        /// Hart ID of the target hart for local events.
+        hart_id: usize,
+    },
+    /// Start receiving software events on the calling hart.
+    ///
+    /// Declared in §17.16.
+    SseHartUnmask,
+    /// Stop receiving software events on the calling hart.
+    ///
+    /// Declared in §17.17.
+    SseHartMask,
+    /// Set the configuration value of a specific firmware feature.
+    ///
+    /// Declared in §18.1.
+    FwftSet {
+        /// Firmware feature ID.
+        feature: u32,
+        /// Configuration value to set.
+        value: usize,
+        /// Flags as defined in module `fwft::flags`.
+        flags: usize,
+    },
+    /// Get the configuration value of a specific firmware feature.
+    ///
+    /// Declared in §18.2.
+    FwftGet {
+        /// Firmware feature ID.
+        feature: u32,
+    },
+    /// Get the number of debug triggers on the calling hart.
+    ///
+    /// Declared in §19.1.
+    DbtrNumTriggers {
+        /// `tdata1` value to filter triggers, or zero for all triggers.
+        trig_tdata1: usize,
+    },
+    /// Set and enable the shared memory for debug trigger configuration on the calling hart.
+    ///
+    /// Declared in §19.2.
+    DbtrSetShmem {
+        /// Low part of the shared memory physical address.
+        shmem_phys_lo: usize,
+        /// High part of the shared memory physical address.
+        shmem_phys_hi: usize,
+        /// Flags, reserved for future use.
+        flags: usize,
+    },
+    /// Read trigger state and configuration into shared memory.
+    ///
+    /// Declared in §19.3.
+    DbtrReadTriggers {
+        /// First trigger index to read.
+        trig_idx_base: usize,
+        /// Number of triggers to read.
+        trig_count: usize,
+    },
+    /// Install debug triggers based on an array of trigger configurations in shared memory.
+    ///
+    /// Declared in §19.4.
+    DbtrInstallTriggers {
+        /// Number of triggers to install.
+        trig_count: usize,
+    },
+    /// Update already installed debug triggers based on a trigger configuration array in shared memory.
+    ///
+    /// Declared in §19.5.
+    DbtrUpdateTriggers {
+        /// Number of triggers to update.
+        trig_count: usize,
+    },
+    /// Uninstall a set of debug triggers.
+    ///
+    /// Declared in §19.6.
+    DbtrUninstallTriggers {
+        /// Base of the trigger index set.
+        trig_idx_base: usize,
+        /// Mask of the trigger index set.
+        trig_idx_mask: usize,
+    },
+    /// Enable a set of debug triggers.
+    ///
+    /// Declared in §19.7.
+    DbtrEnableTriggers {
+        /// Base of the trigger index set.
+        trig_idx_base: usize,
+        /// Mask of the trigger index set.
+        trig_idx_mask: usize,
+    },
+    /// Disable a set of debug triggers.
+    ///
+    /// Declared in §19.8.
+    DbtrDisableTriggers {
+        /// Base of the trigger index set.
+        trig_idx_base: usize,
+        /// Mask of the trigger index set.
+        trig_idx_mask: usize,
+    },
+    /// Get the size of shared memory for message proxy on the calling hart.
+    ///
+    /// Declared in §20.5.
+    MpxyGetShmemSize,
+    /// Set and enable the shared memory for message proxy on the calling hart.
+    ///
+    /// Declared in §20.6.
+    MpxySetShmem {
+        /// Low part of the shared memory physical address.
+        shmem_phys_lo: usize,
+        /// High part of the shared memory physical address.
+        shmem_phys_hi: usize,
+        /// Flags of the shared memory operation.
+        flags: usize,
+    },
+    /// Get channel IDs of the message channels accessible to supervisor software.
+    ///
+    /// Declared in §20.7.
+    MpxyGetChannelIds {
+        /// Index of the first channel ID to return.
+        start_index: u32,
+    },
+    /// Read message channel attributes into shared memory.
+    ///
+    /// Declared in §20.8.
+    MpxyReadAttributes {
+        /// Message channel ID.
+        channel_id: u32,
+        /// First attribute ID to read.
+        base_attribute_id: u32,
+        /// Number of attributes to read.
+        attribute_count: u32,
+    },
+    /// Write message channel attributes from shared memory.
+    ///
+    /// Declared in §20.9.
+    MpxyWriteAttributes {
+        /// Message channel ID.
+        channel_id: u32,
+        /// First attribute ID to write.
+        base_attribute_id: u32,
+        /// Number of attributes to write.
+        attribute_count: u32,
+    },
+    /// Send a message in shared memory to a message channel and wait for its response.
+    ///
+    /// Declared in §20.10.
+    MpxySendMessageWithResponse {
+        /// Message channel ID.
+        channel_id: u32,
+        /// Message ID specific to the message protocol.
+        message_id: u32,
+        /// Length of message data in bytes.
+        message_data_len: usize,
+    },
+    /// Send a message in shared memory to a message channel without waiting for response.
+    ///
+    /// Declared in §20.11.
+    MpxySendMessageWithoutResponse {
+        /// Message channel ID.
+        channel_id: u32,
+        /// Message ID specific to the message protocol.
+        message_id: u32,
+        /// Length of message data in bytes.
+        message_data_len: usize,
+    },
+    /// Get notification events of a message channel into shared memory.
+    ///
+    /// Declared in §20.12.
+    MpxyGetNotificationEvents {
+        /// Message channel ID.
+        channel_id: u32,
+    },
+}
+
+/// Error for an `ecall` whose extension ID or function ID is not a standard SBI function.
+///
+/// SBI implementations would usually respond to such calls with [`SbiRet::not_supported`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownCall {
+    /// Extension ID in register `a7`.
+    pub extension_id: usize,
+    /// Function ID in register `a6`.
+    pub function_id: usize,
+}
+
+impl SbiCall {
+    /// Decodes a standard SBI call from `ecall` registers `a0` to `a7`.
+    ///
+    /// Returns [`UnknownCall`] if the extension ID in `a7` or the function ID in `a6`
+    /// does not name a standard SBI function.
+    pub const fn decode(regs: [usize; 8]) -> Result<Self, UnknownCall> {
+        use crate::{
+            base, cppc, dbcn, dbtr, fwft, hsm, mpxy, nacl, pmu, rfnc, spi, srst, sse, sta, susp,
+            time,
+        };
+        let [a0, a1, a2, a3, a4, a5, fid, eid] = regs;
+        let call = match eid {
+            #[cfg(feature = "legacy")]
+            crate::legacy::LEGACY_SET_TIMER => Self::LegacySetTimer {
+                stime_value: join_u64(a0, a1),
+            },
+            #[cfg(feature = "legacy")]
+            crate::legacy::LEGACY_CONSOLE_PUTCHAR => Self::LegacyConsolePutchar { ch: a0 },
+            #[cfg(feature = "legacy")]
+            crate::legacy::LEGACY_CONSOLE_GETCHAR => Self::LegacyConsoleGetchar,
+            #[cfg(feature = "legacy")]
+            crate::legacy::LEGACY_CLEAR_IPI => Self::LegacyClearIpi,
+            #[cfg(feature = "legacy")]
+            crate::legacy::LEGACY_SEND_IPI => Self::LegacySendIpi { hart_mask: a0 },
+            #[cfg(feature = "legacy")]
+            crate::legacy::LEGACY_REMOTE_FENCE_I => Self::LegacyRemoteFenceI { hart_mask: a0 },
+            #[cfg(feature = "legacy")]
+            crate::legacy::LEGACY_REMOTE_SFENCE_VMA => Self::LegacyRemoteSfenceVma {
+                hart_mask: a0,
+                start: a1,
+                size: a2,
+            },
+            #[cfg(feature = "legacy")]
+            crate::legacy::LEGACY_REMOTE_SFENCE_VMA_ASID => Self::LegacyRemoteSfenceVmaAsid {
+                hart_mask: a0,
+                start: a1,
+                size: a2,
+                asid: a3,
+            },
+            #[cfg(feature = "legacy")]
+            crate::legacy::LEGACY_SHUTDOWN => Self::LegacyShutdown,
+            base::EID_BASE => match fid {
+                base::GET_SBI_SPEC_VERSION => Self::BaseGetSpecVersion,
+                base::GET_SBI_IMPL_ID => Self::BaseGetImplId,
+                base::GET_SBI_IMPL_VERSION => Self::BaseGetImplVersion,
+                base::PROBE_EXTENSION => Self::BaseProbeExtension { extension_id: a0 },
+                base::GET_MVENDORID => Self::BaseGetMvendorid,
+                base::GET_MARCHID => Self::BaseGetMarchid,
+                base::GET_MIMPID => Self::BaseGetMimpid,
+                _ => return Err(UnknownCall::new(eid, fid)),
+            },
+            time::EID_TIME => match fid {
+                time::SET_TIMER => Self::TimeSetTimer {
+                    stime_value: join_u64(a0, a1),
+                },
+                _ => return Err(UnknownCall::new(eid, fid)),
+            },
+            spi::EID_SPI => match fid {
+                spi::SEND_IPI => Self::SpiSendIpi {
+                    hart_mask: HartMask::from_mask_base(a0, a1),
+                },
+                _ => return Err(UnknownCall::new(eid, fid)),
+            },
+            rfnc::EID_RFNC => {
+                let hart_mask = HartMask::from_mask_base(a0, a1);
+                match fid {
+                    rfnc::REMOTE_FENCE_I => Self::RfncRemoteFenceI { hart_mask },
+                    rfnc::REMOTE_SFENCE_VMA => Self::RfncRemoteSfenceVma {
+                        hart_mask,
+                        start: a2,
+                        size: a3,
+                    },
+                    rfnc::REMOTE_SFENCE_VMA_ASID => Self::RfncRemoteSfenceVmaAsid {
+                        hart_mask,
+                        start: a2,
+                        size: a3,
+                        asid: a4,
+                    },
+                    rfnc::REMOTE_HFENCE_GVMA_VMID => Self::RfncRemoteHfenceGvmaVmid {
+                        hart_mask,
+                        start: a2,
+                        size: a3,
+                        vmid: a4,
+                    },
+                    rfnc::REMOTE_HFENCE_GVMA => Self::RfncRemoteHfenceGvma {
+                        hart_mask,
+                        start: a2,
+                        size: a3,
+                    },
+                    rfnc::REMOTE_HFENCE_VVMA_ASID => Self::RfncRemoteHfenceVvmaAsid {
+                        hart_mask,
+                        start: a2,
+                        size: a3,
+                        asid: a4,
+                    },
+                    rfnc::REMOTE_HFENCE_VVMA => Self::RfncRemoteHfenceVvma {
+                        hart_mask,
+                        start: a2,
+                        size: a3,
+                    },
+                    _ => return Err(UnknownCall::new(eid, fid)),
+                }
+            }
+            hsm::EID_HSM => match fid {
+                hsm::HART_START => Self::HsmHartStart {
+                    hartid: a0,
+                    start_addr: a1,
+                    opaque: a2,
+                },
+                hsm::HART_STOP => Self::HsmHartStop,
+                hsm::HART_GET_STATUS => Self::HsmHartGetStatus { hartid: a0 },
+                hsm::HART_SUSPEND => Self::HsmHartSuspend {
+                    suspend_type: hsm::SuspendType::from_raw(a0 as u32),
+                    resume_addr: a1,
+                    opaque: a2,
+                },
+                _ => return Err(UnknownCall::new(eid, fid)),
+            },
+            srst::EID_SRST => match fid {
+                srst::SYSTEM_RESET => Self::SrstSystemReset {
+                    reset_type: srst::ResetType::from_raw(a0 as u32),
+                    reset_reason: srst::ResetReason::from_raw(a1 as u32),
+                },
+                _ => return Err(UnknownCall::new(eid, fid)),
+            },
+            pmu::EID_PMU => match fid {
+                pmu::NUM_COUNTERS => Self::PmuNumCounters,
+                pmu::COUNTER_GET_INFO => Self::PmuCounterGetInfo { counter_idx: a0 },
+                pmu::COUNTER_CONFIG_MATCHING => Self::PmuCounterConfigMatching {
+                    counter_mask: pmu::CounterMask::from_mask_base(a1, a0),
+                    config_flags: pmu::ConfigFlags::from_raw(a2),
+                    event_idx: a3,
+                    event_data: join_u64(a4, a5),
+                },
+                pmu::COUNTER_START => Self::PmuCounterStart {
+                    counter_mask: pmu::CounterMask::from_mask_base(a1, a0),
+                    start_flags: pmu::StartFlags::from_raw(a2),
+                    initial_value: join_u64(a3, a4),
+                },
+                pmu::COUNTER_STOP => Self::PmuCounterStop {
+                    counter_mask: pmu::CounterMask::from_mask_base(a1, a0),
+                    stop_flags: pmu::StopFlags::from_raw(a2),
+                },
+                pmu::COUNTER_FW_READ => Self::PmuCounterFwRead { counter_idx: a0 },
+                pmu::COUNTER_FW_READ_HI => Self::PmuCounterFwReadHi { counter_idx: a0 },
+                pmu::SNAPSHOT_SET_SHMEM => Self::PmuSnapshotSetShmem {
+                    shmem_phys_lo: a0,
+                    shmem_phys_hi: a1,
+                    flags: a2,
+                },
+                _ => return Err(UnknownCall::new(eid, fid)),
+            },
+            dbcn::EID_DBCN => match fid {
+                dbcn::CONSOLE_WRITE => Self::DbcnConsoleWrite {
+                    num_bytes: a0,
+                    base_addr_lo: a1,
+                    base_addr_hi: a2,
+                },
+                dbcn::CONSOLE_READ => Self::DbcnConsoleRead {
+                    num_bytes: a0,
+                    base_addr_lo: a1,
+                    base_addr_hi: a2,
+                },
+                dbcn::CONSOLE_WRITE_BYTE => Self::DbcnConsoleWriteByte { byte: a0 as u8 },
+                _ => return Err(UnknownCall::new(eid, fid)),
+            },
+            susp::EID_SUSP => match fid {
+                susp::SUSPEND => Self::SuspSuspend {
+                    sleep_type: susp::SleepType::from_raw(a0 as u32),
+                    resume_addr: a1,
+                    opaque: a2,
+                },
+                _ => return Err(UnknownCall::new(eid, fid)),
+            },
+            cppc::EID_CPPC => match fid {
+                cppc::PROBE => Self::CppcProbe {
+                    reg_id: cppc::CppcReg::from_raw(a0 as u32),
+                },
+                cppc::READ => Self::CppcRead {
+                    reg_id: cppc::CppcReg::from_raw(a0 as u32),
+                },
+                cppc::READ_HI => Self::CppcReadHi {
+                    reg_id: cppc::CppcReg::from_raw(a0 as u32),
+                },
+                cppc::WRITE => Self::CppcWrite {
+                    reg_id: cppc::CppcReg::from_raw(a0 as u32),
+                    value: join_u64(a1, a2),
+                },
+                _ => return Err(UnknownCall::new(eid, fid)),
+            },
+            nacl::EID_NACL => match fid {
+                nacl::PROBE_FEATURE => Self::NaclProbeFeature {
+                    feature_id: a0 as u32,
+                },
+                nacl::SET_SHMEM => Self::NaclSetShmem {
+                    shmem_phys_lo: a0,
+                    shmem_phys_hi: a1,
+                    flags: a2,
+                },
+                nacl::SYNC_CSR => Self::NaclSyncCsr { csr_num: a0 },
+                nacl::SYNC_HFENCE => Self::NaclSyncHfence { entry_index: a0 },
+                nacl::SYNC_SRET => Self::NaclSyncSret,
+                _ => return Err(UnknownCall::new(eid, fid)),
+            },
+            sta::EID_STA => match fid {
+                sta::SET_SHMEM => Self::StaSetShmem {
+                    shmem_phys_lo: a0,
+                    shmem_phys_hi: a1,
+                    flags: a2,
+                },
+                _ => return Err(UnknownCall::new(eid, fid)),
+            },
+            sse::EID_SSE => match fid {
+                sse::READ_ATTRS => Self::SseReadAttrs {
+                    event_id: a0 as u32,
+                    base_attr_id: a1 as u32,
+                    attr_count: a2,
+                    output_phys_lo: a3,
+                    output_phys_hi: a4,
+                },
+                sse::WRITE_ATTRS => Self::SseWriteAttrs {
+                    event_id: a0 as u32,
+                    base_attr_id: a1 as u32,
+                    attr_count: a2,
+                    input_phys_lo: a3,
+                    input_phys_hi: a4,
+                },
+                sse::REGISTER => Self::SseRegister {
+                    event_id: a0 as u32,
+                    handler_entry_pc: a1,
+                    handler_entry_arg: a2,
+                },
+                sse::UNREGISTER => Self::SseUnregister {
+                    event_id: a0 as u32,
+                },
+                sse::ENABLE => Self::SseEnable {
+                    event_id: a0 as u32,
+                },
+                sse::DISABLE => Self::SseDisable {
+                    event_id: a0 as u32,
+                },
+                sse::COMPLETE => Self::SseComplete,
+                sse::INJECT => Self::SseInject {
+                    event_id: a0 as u32,
+                    hart_id: a1,
+                },
+                sse::HART_UNMASK => Self::SseHartUnmask,
+                sse::HART_MASK => Self::SseHartMask,
+                _ => return Err(UnknownCall::new(eid, fid)),
+            },
+            fwft::EID_FWFT => match fid {
+                fwft::SET => Self::FwftSet {
+                    feature: a0 as u32,
+                    value: a1,
+                    flags: a2,
+                },
+                fwft::GET => Self::FwftGet { feature: a0 as u32 },
+                _ => return Err(UnknownCall::new(eid, fid)),
+            },
+            dbtr::EID_DBTR => match fid {
+                dbtr::NUM_TRIGGERS => Self::DbtrNumTriggers { trig_tdata1: a0 },
+                dbtr::SET_SHMEM => Self::DbtrSetShmem {
+                    shmem_phys_lo: a0,
+                    shmem_phys_hi: a1,
+                    flags: a2,
+                },
+                dbtr::READ_TRIGGERS => Self::DbtrReadTriggers {
+                    trig_idx_base: a0,
+                    trig_count: a1,
+                },
+                dbtr::INSTALL_TRIGGERS => Self::DbtrInstallTriggers { trig_count: a0 },
+                dbtr::UPDATE_TRIGGERS => Self::DbtrUpdateTriggers { trig_count: a0 },
+                dbtr::UNINSTALL_TRIGGERS => Self::DbtrUninstallTriggers {
+                    trig_idx_base: a0,
+                    trig_idx_mask: a1,
+                },
+                dbtr::ENABLE_TRIGGERS => Self::DbtrEnableTriggers {
+                    trig_idx_base: a0,
+                    trig_idx_mask: a1,
+                },
+                dbtr::DISABLE_TRIGGERS => Self::DbtrDisableTriggers {
+                    trig_idx_base: a0,
+                    trig_idx_mask: a1,
+                },
+                _ => return Err(UnknownCall::new(eid, fid)),
+            },
+            mpxy::EID_MPXY => match fid {
+                mpxy::GET_SHMEM_SIZE => Self::MpxyGetShmemSize,
+                mpxy::SET_SHMEM => Self::MpxySetShmem {
+                    shmem_phys_lo: a0,
+                    shmem_phys_hi: a1,
+                    flags: a2,
+                },
+                mpxy::GET_CHANNEL_IDS => Self::MpxyGetChannelIds {
+                    start_index: a0 as u32,
+                },
+                mpxy::READ_ATTRIBUTES => Self::MpxyReadAttributes {
+                    channel_id: a0 as u32,
+                    base_attribute_id: a1 as u32,
+                    attribute_count: a2 as u32,
+                },
+                mpxy::WRITE_ATTRIBUTES => Self::MpxyWriteAttributes {
+                    channel_id: a0 as u32,
+                    base_attribute_id: a1 as u32,
+                    attribute_count: a2 as u32,
+                },
+                mpxy::SEND_MESSAGE_WITH_RESPONSE => Self::MpxySendMessageWithResponse {
+                    channel_id: a0 as u32,
+                    message_id: a1 as u32,
+                    message_data_len: a2,
+                },
+                mpxy::SEND_MESSAGE_WITHOUT_RESPONSE => Self::MpxySendMessageWithoutResponse {
+                    channel_id: a0 as u32,
+                    message_id: a1 as u32,
+                    message_data_len: a2,
+                },
+                mpxy::GET_NOTIFICATION_EVENTS => Self::MpxyGetNotificationEvents {
+                    channel_id: a0 as u32,
+                },
+                _ => return Err(UnknownCall::new(eid, fid)),
+            },
+            _ => return Err(UnknownCall::new(eid, fid)),
+        };
+        Ok(call)
+    }
+
+    /// Encodes this SBI call into `ecall` registers `a0` to `a7`.
+    ///
+    /// Registers `a0` to `a5` hold function parameters, `a6` holds the function ID
+    /// and `a7` holds the extension ID. Registers not used by the function are zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sbi_spec::{binary::{HartMask, SbiCall}, spi};
+    /// let call = SbiCall::SpiSendIpi {
+    ///     hart_mask: HartMask::from_mask_base(0b11, 4),
+    /// };
+    /// assert_eq!(call.encode(), [0b11, 4, 0, 0, 0, 0, spi::SEND_IPI, spi::EID_SPI]);
+    /// ```
+    pub const fn encode(self) -> [usize; 8] {
+        use crate::{
+            base, cppc, dbcn, dbtr, fwft, hsm, mpxy, nacl, pmu, rfnc, spi, srst, sse, sta, susp,
+            time,
+        };
+        match self {
+            #[cfg(feature = "legacy")]
+            Self::LegacySetTimer { stime_value } => {
+                let (lo, hi) = split_u64(stime_value);
+                [lo, hi, 0, 0, 0, 0, 0, crate::legacy::LEGACY_SET_TIMER]
+            }
+            #[cfg(feature = "legacy")]
+            Self::LegacyConsolePutchar { ch } => {
+                [ch, 0, 0, 0, 0, 0, 0, crate::legacy::LEGACY_CONSOLE_PUTCHAR]
+            }
+            #[cfg(feature = "legacy")]
+            Self::LegacyConsoleGetchar => {
+                [0, 0, 0, 0, 0, 0, 0, crate::legacy::LEGACY_CONSOLE_GETCHAR]
+            }
+            #[cfg(feature = "legacy")]
+            Self::LegacyClearIpi => [0, 0, 0, 0, 0, 0, 0, crate::legacy::LEGACY_CLEAR_IPI],
+            #[cfg(feature = "legacy")]
+            Self::LegacySendIpi { hart_mask } => {
+                [hart_mask, 0, 0, 0, 0, 0, 0, crate::legacy::LEGACY_SEND_IPI]
+            }
+            #[cfg(feature = "legacy")]
+            Self::LegacyRemoteFenceI { hart_mask } => [
+                hart_mask,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                crate::legacy::LEGACY_REMOTE_FENCE_I,
+            ],
+            #[cfg(feature = "legacy")]
+            Self::LegacyRemoteSfenceVma {
+                hart_mask,
+                start,
+                size,
+            } => [
+                hart_mask,
+                start,
+                size,
+                0,
+                0,
+                0,
+                0,
+                crate::legacy::LEGACY_REMOTE_SFENCE_VMA,
+            ],
+            #[cfg(feature = "legacy")]
+            Self::LegacyRemoteSfenceVmaAsid {
+                hart_mask,
+                start,
+                size,
+                asid,
+            } => [
+                hart_mask,
+                start,
+                size,
+                asid,
+                0,
+                0,
+                0,
+                crate::legacy::LEGACY_REMOTE_SFENCE_VMA_ASID,
+            ],
+            #[cfg(feature = "legacy")]
+            Self::LegacyShutdown => [0, 0, 0, 0, 0, 0, 0, crate::legacy::LEGACY_SHUTDOWN],
+            Self::BaseGetSpecVersion => {
+                [0, 0, 0, 0, 0, 0, base::GET_SBI_SPEC_VERSION, base::EID_BASE]
+            }
+            Self::BaseGetImplId => [0, 0, 0, 0, 0, 0, base::GET_SBI_IMPL_ID, base::EID_BASE],
+            Self::BaseGetImplVersion => {
+                [0, 0, 0, 0, 0, 0, base::GET_SBI_IMPL_VERSION, base::EID_BASE]
+            }
+            Self::BaseProbeExtension { extension_id } => [
+                extension_id,
+                0,
+                0,
+                0,
+                0,
+                0,
+                base::PROBE_EXTENSION,
+                base::EID_BASE,
+            ],
+            Self::BaseGetMvendorid => [0, 0, 0, 0, 0, 0, base::GET_MVENDORID, base::EID_BASE],
+            Self::BaseGetMarchid => [0, 0, 0, 0, 0, 0, base::GET_MARCHID, base::EID_BASE],
+            Self::BaseGetMimpid => [0, 0, 0, 0, 0, 0, base::GET_MIMPID, base::EID_BASE],
+            Self::TimeSetTimer { stime_value } => {
+                let (lo, hi) = split_u64(stime_value);
+                [lo, hi, 0, 0, 0, 0, time::SET_TIMER, time::EID_TIME]
+            }
+            Self::SpiSendIpi { hart_mask } => {
+                let (mask, base) = hart_mask.into_inner();
+                [mask, base, 0, 0, 0, 0, spi::SEND_IPI, spi::EID_SPI]
+            }
+            Self::RfncRemoteFenceI { hart_mask } => {
+                let (mask, base) = hart_mask.into_inner();
+                [mask, base, 0, 0, 0, 0, rfnc::REMOTE_FENCE_I, rfnc::EID_RFNC]
+            }
+            Self::RfncRemoteSfenceVma {
+                hart_mask,
+                start,
+                size,
+            } => {
+                let (mask, base) = hart_mask.into_inner();
+                [
+                    mask,
+                    base,
+                    start,
+                    size,
+                    0,
+                    0,
+                    rfnc::REMOTE_SFENCE_VMA,
+                    rfnc::EID_RFNC,
+                ]
+            }
+            Self::RfncRemoteSfenceVmaAsid {
+                hart_mask,
+                start,
+                size,
+                asid,
+            } => {
+                let (mask, base) = hart_mask.into_inner();
+                [
+                    mask,
+                    base,
+                    start,
+                    size,
+                    asid,
+                    0,
+                    rfnc::REMOTE_SFENCE_VMA_ASID,
+                    rfnc::EID_RFNC,
+                ]
+            }
+            Self::RfncRemoteHfenceGvmaVmid {
+                hart_mask,
+                start,
+                size,
+                vmid,
+            } => {
+                let (mask, base) = hart_mask.into_inner();
+                [
+                    mask,
+                    base,
+                    start,
+                    size,
+                    vmid,
+                    0,
+                    rfnc::REMOTE_HFENCE_GVMA_VMID,
+                    rfnc::EID_RFNC,
+                ]
+            }
+            Self::RfncRemoteHfenceGvma {
+                hart_mask,
+                start,
+                size,
+            } => {
+                let (mask, base) = hart_mask.into_inner();
+                [
+                    mask,
+                    base,
+                    start,
+                    size,
+                    0,
+                    0,
+                    rfnc::REMOTE_HFENCE_GVMA,
+                    rfnc::EID_RFNC,
+                ]
+            }
+            Self::RfncRemoteHfenceVvmaAsid {
+                hart_mask,
+                start,
+                size,
+                asid,
+            } => {
+                let (mask, base) = hart_mask.into_inner();
+                [
+                    mask,
+                    base,
+                    start,
+                    size,
+                    asid,
+                    0,
+                    rfnc::REMOTE_HFENCE_VVMA_ASID,
+                    rfnc::EID_RFNC,
+                ]
+            }
+            Self::RfncRemoteHfenceVvma {
+                hart_mask,
+                start,
+                size,
+            } => {
+                let (mask, base) = hart_mask.into_inner();
+                [
+                    mask,
+                    base,
+                    start,
+                    size,
+                    0,
+                    0,
+                    rfnc::REMOTE_HFENCE_VVMA,
+                    rfnc::EID_RFNC,
+                ]
+            }
+            Self::HsmHartStart {
+                hartid,
+                start_addr,
+                opaque,
+            } => [
+                hartid,
+                start_addr,
+                opaque,
+                0,
+                0,
+                0,
+                hsm::HART_START,
+                hsm::EID_HSM,
+            ],
+            Self::HsmHartStop => [0, 0, 0, 0, 0, 0, hsm::HART_STOP, hsm::EID_HSM],
+            Self::HsmHartGetStatus { hartid } => {
+                [hartid, 0, 0, 0, 0, 0, hsm::HART_GET_STATUS, hsm::EID_HSM]
+            }
+            Self::HsmHartSuspend {
+                suspend_type,
+                resume_addr,
+                opaque,
+            } => [
+                suspend_type.raw() as usize,
+                resume_addr,
+                opaque,
+                0,
+                0,
+                0,
+                hsm::HART_SUSPEND,
+                hsm::EID_HSM,
+            ],
+            Self::SrstSystemReset {
+                reset_type,
+                reset_reason,
+            } => [
+                reset_type.raw() as usize,
+                reset_reason.raw() as usize,
+                0,
+                0,
+                0,
+                0,
+                srst::SYSTEM_RESET,
+                srst::EID_SRST,
+            ],
+            Self::PmuNumCounters => [0, 0, 0, 0, 0, 0, pmu::NUM_COUNTERS, pmu::EID_PMU],
+            Self::PmuCounterGetInfo { counter_idx } => [
+                counter_idx,
+                0,
+                0,
+                0,
+                0,
+                0,
+                pmu::COUNTER_GET_INFO,
+                pmu::EID_PMU,
+            ],
+            Self::PmuCounterConfigMatching {
+                counter_mask,
+                config_flags,
+                event_idx,
+                event_data,
+            } => {
+                let (mask, base) = counter_mask.into_inner();
+                let (lo, hi) = split_u64(event_data);
+                [
+                    base,
+                    mask,
+                    config_flags.raw(),
+                    event_idx,
+                    lo,
+                    hi,
+                    pmu::COUNTER_CONFIG_MATCHING,
+                    pmu::EID_PMU,
+                ]
+            }
+            Self::PmuCounterStart {
+                counter_mask,
+                start_flags,
+                initial_value,
+            } => {
+                let (mask, base) = counter_mask.into_inner();
+                let (lo, hi) = split_u64(initial_value);
+                [
+                    base,
+                    mask,
+                    start_flags.raw(),
+                    lo,
+                    hi,
+                    0,
+                    pmu::COUNTER_START,
+                    pmu::EID_PMU,
+                ]
+            }
+            Self::PmuCounterStop {
+                counter_mask,
+                stop_flags,
+            } => {
+                let (mask, base) = counter_mask.into_inner();
+                [
+                    base,
+                    mask,
+                    stop_flags.raw(),
+                    0,
+                    0,
+                    0,
+                    pmu::COUNTER_STOP,
+                    pmu::EID_PMU,
+                ]
+            }
+            Self::PmuCounterFwRead { counter_idx } => [
+                counter_idx,
+                0,
+                0,
+                0,
+                0,
+                0,
+                pmu::COUNTER_FW_READ,
+                pmu::EID_PMU,
+            ],
+            Self::PmuCounterFwReadHi { counter_idx } => [
+                counter_idx,
+                0,
+                0,
+                0,
+                0,
+                0,
+                pmu::COUNTER_FW_READ_HI,
+                pmu::EID_PMU,
+            ],
+            Self::PmuSnapshotSetShmem {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+            } => [
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+                0,
+                0,
+                0,
+                pmu::SNAPSHOT_SET_SHMEM,
+                pmu::EID_PMU,
+            ],
+            Self::DbcnConsoleWrite {
+                num_bytes,
+                base_addr_lo,
+                base_addr_hi,
+            } => [
+                num_bytes,
+                base_addr_lo,
+                base_addr_hi,
+                0,
+                0,
+                0,
+                dbcn::CONSOLE_WRITE,
+                dbcn::EID_DBCN,
+            ],
+            Self::DbcnConsoleRead {
+                num_bytes,
+                base_addr_lo,
+                base_addr_hi,
+            } => [
+                num_bytes,
+                base_addr_lo,
+                base_addr_hi,
+                0,
+                0,
+                0,
+                dbcn::CONSOLE_READ,
+                dbcn::EID_DBCN,
+            ],
+            Self::DbcnConsoleWriteByte { byte } => [
+                byte as usize,
+                0,
+                0,
+                0,
+                0,
+                0,
+                dbcn::CONSOLE_WRITE_BYTE,
+                dbcn::EID_DBCN,
+            ],
+            Self::SuspSuspend {
+                sleep_type,
+                resume_addr,
+                opaque,
+            } => [
+                sleep_type.raw() as usize,
+                resume_addr,
+                opaque,
+                0,
+                0,
+                0,
+                susp::SUSPEND,
+                susp::EID_SUSP,
+            ],
+            Self::CppcProbe { reg_id } => [
+                reg_id.raw() as usize,
+                0,
+                0,
+                0,
+                0,
+                0,
+                cppc::PROBE,
+                cppc::EID_CPPC,
+            ],
+            Self::CppcRead { reg_id } => [
+                reg_id.raw() as usize,
+                0,
+                0,
+                0,
+                0,
+                0,
+                cppc::READ,
+                cppc::EID_CPPC,
+            ],
+            Self::CppcReadHi { reg_id } => [
+                reg_id.raw() as usize,
+                0,
+                0,
+                0,
+                0,
+                0,
+                cppc::READ_HI,
+                cppc::EID_CPPC,
+            ],
+            Self::CppcWrite { reg_id, value } => {
+                let (lo, hi) = split_u64(value);
+                [
+                    reg_id.raw() as usize,
+                    lo,
+                    hi,
+                    0,
+                    0,
+                    0,
+                    cppc::WRITE,
+                    cppc::EID_CPPC,
+                ]
+            }
+            Self::NaclProbeFeature { feature_id } => [
+                feature_id as usize,
+                0,
+                0,
+                0,
+                0,
+                0,
+                nacl::PROBE_FEATURE,
+                nacl::EID_NACL,
+            ],
+            Self::NaclSetShmem {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+            } => [
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+                0,
+                0,
+                0,
+                nacl::SET_SHMEM,
+                nacl::EID_NACL,
+            ],
+            Self::NaclSyncCsr { csr_num } => {
+                [csr_num, 0, 0, 0, 0, 0, nacl::SYNC_CSR, nacl::EID_NACL]
+            }
+            Self::NaclSyncHfence { entry_index } => [
+                entry_index,
+                0,
+                0,
+                0,
+                0,
+                0,
+                nacl::SYNC_HFENCE,
+                nacl::EID_NACL,
+            ],
+            Self::NaclSyncSret => [0, 0, 0, 0, 0, 0, nacl::SYNC_SRET, nacl::EID_NACL],
+            Self::StaSetShmem {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+            } => [
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+                0,
+                0,
+                0,
+                sta::SET_SHMEM,
+                sta::EID_STA,
+            ],
+            Self::SseReadAttrs {
+                event_id,
+                base_attr_id,
+                attr_count,
+                output_phys_lo,
+                output_phys_hi,
+            } => [
+                event_id as usize,
+                base_attr_id as usize,
+                attr_count,
+                output_phys_lo,
+                output_phys_hi,
+                0,
+                sse::READ_ATTRS,
+                sse::EID_SSE,
+            ],
+            Self::SseWriteAttrs {
+                event_id,
+                base_attr_id,
+                attr_count,
+                input_phys_lo,
+                input_phys_hi,
+            } => [
+                event_id as usize,
+                base_attr_id as usize,
+                attr_count,
+                input_phys_lo,
+                input_phys_hi,
+                0,
+                sse::WRITE_ATTRS,
+                sse::EID_SSE,
+            ],
+            Self::SseRegister {
+                event_id,
+                handler_entry_pc,
+                handler_entry_arg,
+            } => [
+                event_id as usize,
+                handler_entry_pc,
+                handler_entry_arg,
+                0,
+                0,
+                0,
+                sse::REGISTER,
+                sse::EID_SSE,
+            ],
+            Self::SseUnregister { event_id } => [
+                event_id as usize,
+                0,
+                0,
+                0,
+                0,
+                0,
+                sse::UNREGISTER,
+                sse::EID_SSE,
+            ],
+            Self::SseEnable { event_id } => {
+                [event_id as usize, 0, 0, 0, 0, 0, sse::ENABLE, sse::EID_SSE]
+            }
+            Self::SseDisable { event_id } => {
+                [event_id as usize, 0, 0, 0, 0, 0, sse::DISABLE, sse::EID_SSE]
+            }
+            Self::SseComplete => [0, 0, 0, 0, 0, 0, sse::COMPLETE, sse::EID_SSE],
+            Self::SseInject { event_id, hart_id } => [
+                event_id as usize,
+                hart_id,
+                0,
+                0,
+                0,
+                0,
+                sse::INJECT,
+                sse::EID_SSE,
+            ],
+            Self::SseHartUnmask => [0, 0, 0, 0, 0, 0, sse::HART_UNMASK, sse::EID_SSE],
+            Self::SseHartMask => [0, 0, 0, 0, 0, 0, sse::HART_MASK, sse::EID_SSE],
+            Self::FwftSet {
+                feature,
+                value,
+                flags,
+            } => [
+                feature as usize,
+                value,
+                flags,
+                0,
+                0,
+                0,
+                fwft::SET,
+                fwft::EID_FWFT,
+            ],
+            Self::FwftGet { feature } => {
+                [feature as usize, 0, 0, 0, 0, 0, fwft::GET, fwft::EID_FWFT]
+            }
+            Self::DbtrNumTriggers { trig_tdata1 } => [
+                trig_tdata1,
+                0,
+                0,
+                0,
+                0,
+                0,
+                dbtr::NUM_TRIGGERS,
+                dbtr::EID_DBTR,
+            ],
+            Self::DbtrSetShmem {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+            } => [
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+                0,
+                0,
+                0,
+                dbtr::SET_SHMEM,
+                dbtr::EID_DBTR,
+            ],
+            Self::DbtrReadTriggers {
+                trig_idx_base,
+                trig_count,
+            } => [
+                trig_idx_base,
+                trig_count,
+                0,
+                0,
+                0,
+                0,
+                dbtr::READ_TRIGGERS,
+                dbtr::EID_DBTR,
+            ],
+            Self::DbtrInstallTriggers { trig_count } => [
+                trig_count,
+                0,
+                0,
+                0,
+                0,
+                0,
+                dbtr::INSTALL_TRIGGERS,
+                dbtr::EID_DBTR,
+            ],
+            Self::DbtrUpdateTriggers { trig_count } => [
+                trig_count,
+                0,
+                0,
+                0,
+                0,
+                0,
+                dbtr::UPDATE_TRIGGERS,
+                dbtr::EID_DBTR,
+            ],
+            Self::DbtrUninstallTriggers {
+                trig_idx_base,
+                trig_idx_mask,
+            } => [
+                trig_idx_base,
+                trig_idx_mask,
+                0,
+                0,
+                0,
+                0,
+                dbtr::UNINSTALL_TRIGGERS,
+                dbtr::EID_DBTR,
+            ],
+            Self::DbtrEnableTriggers {
+                trig_idx_base,
+                trig_idx_mask,
+            } => [
+                trig_idx_base,
+                trig_idx_mask,
+                0,
+                0,
+                0,
+                0,
+                dbtr::ENABLE_TRIGGERS,
+                dbtr::EID_DBTR,
+            ],
+            Self::DbtrDisableTriggers {
+                trig_idx_base,
+                trig_idx_mask,
+            } => [
+                trig_idx_base,
+                trig_idx_mask,
+                0,
+                0,
+                0,
+                0,
+                dbtr::DISABLE_TRIGGERS,
+                dbtr::EID_DBTR,
+            ],
+            Self::MpxyGetShmemSize => [0, 0, 0, 0, 0, 0, mpxy::GET_SHMEM_SIZE, mpxy::EID_MPXY],
+            Self::MpxySetShmem {
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+            } => [
+                shmem_phys_lo,
+                shmem_phys_hi,
+                flags,
+                0,
+                0,
+                0,
+                mpxy::SET_SHMEM,
+                mpxy::EID_MPXY,
+            ],
+            Self::MpxyGetChannelIds { start_index } => [
+                start_index as usize,
+                0,
+                0,
+                0,
+                0,
+                0,
+                mpxy::GET_CHANNEL_IDS,
+                mpxy::EID_MPXY,
+            ],
+            Self::MpxyReadAttributes {
+                channel_id,
+                base_attribute_id,
+                attribute_count,
+            } => [
+                channel_id as usize,
+                base_attribute_id as usize,
+                attribute_count as usize,
+                0,
+                0,
+                0,
+                mpxy::READ_ATTRIBUTES,
+                mpxy::EID_MPXY,
+            ],
+            Self::MpxyWriteAttributes {
+                channel_id,
+                base_attribute_id,
+                attribute_count,
+            } => [
+                channel_id as usize,
+                base_attribute_id as usize,
+                attribute_count as usize,
+                0,
+                0,
+                0,
+                mpxy::WRITE_ATTRIBUTES,
+                mpxy::EID_MPXY,
+            ],
+            Self::MpxySendMessageWithResponse {
+                channel_id,
+                message_id,
+                message_data_len,
+            } => [
+                channel_id as usize,
+                message_id as usize,
+                message_data_len,
+                0,
+                0,
+                0,
+                mpxy::SEND_MESSAGE_WITH_RESPONSE,
+                mpxy::EID_MPXY,
+            ],
+            Self::MpxySendMessageWithoutResponse {
+                channel_id,
+                message_id,
+                message_data_len,
+            } => [
+                channel_id as usize,
+                message_id as usize,
+                message_data_len,
+                0,
+                0,
+                0,
+                mpxy::SEND_MESSAGE_WITHOUT_RESPONSE,
+                mpxy::EID_MPXY,
+            ],
+            Self::MpxyGetNotificationEvents { channel_id } => [
+                channel_id as usize,
+                0,
+                0,
+                0,
+                0,
+                0,
+                mpxy::GET_NOTIFICATION_EVENTS,
+                mpxy::EID_MPXY,
+            ],
+        }
+    }
+
+    /// Creates a call to program the clock for next event after an absolute time.
+    ///
+    /// The 64-bit `stime_value` is passed in `a0` on RV64, or split into `a0` and `a1` on RV32.
+    #[inline]
+    pub const fn time_set_timer(stime_value: u64) -> Self {
+        Self::TimeSetTimer { stime_value }
+    }
+
+    /// Creates calls to read the 64-bit value of a firmware counter.
+    ///
+    /// Returns the `COUNTER_FW_READ` call, and on RV32 also the `COUNTER_FW_READ_HI` call
+    /// for the upper 32 bits. Join their returned values with [`join_u64`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sbi_spec::binary::SbiCall;
+    /// let (lo, hi) = SbiCall::pmu_counter_fw_read(3);
+    /// assert_eq!(lo, SbiCall::PmuCounterFwRead { counter_idx: 3 });
+    /// # #[cfg(target_pointer_width = "64")]
+    /// assert_eq!(hi, None);
+    /// ```
+    #[inline]
+    pub const fn pmu_counter_fw_read(counter_idx: usize) -> (Self, Option<Self>) {
+        let hi = if cfg!(target_pointer_width = "32") {
+            Some(Self::PmuCounterFwReadHi { counter_idx })
+        } else {
+            None
+        };
+        (Self::PmuCounterFwRead { counter_idx }, hi)
+    }
+
+    /// Creates calls to read the value of a CPPC register.
+    ///
+    /// Returns the `READ` call, and on RV32 also the `READ_HI` call for the upper 32 bits
+    /// of registers that may be 64 bits wide. Join their returned values with [`join_u64`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sbi_spec::{binary::SbiCall, cppc::CppcReg};
+    /// let (lo, hi) = SbiCall::cppc_read(CppcReg::DesiredPerformance);
+    /// assert_eq!(lo, SbiCall::CppcRead { reg_id: CppcReg::DesiredPerformance });
+    /// assert_eq!(hi, None);
+    /// ```
+    #[inline]
+    pub const fn cppc_read(reg_id: crate::cppc::CppcReg) -> (Self, Option<Self>) {
+        let hi = match reg_id.width() {
+            Some(width) if width.max() > 32 && cfg!(target_pointer_width = "32") => {
+                Some(Self::CppcReadHi { reg_id })
+            }
+            _ => None,
+        };
+        (Self::CppcRead { reg_id }, hi)
+    }
+
+    /// Creates a call to write a 64-bit value into a CPPC register.
+    ///
+    /// The 64-bit `value` is passed in `a1` on RV64, or split into `a1` and `a2` on RV32.
+    #[inline]
+    pub const fn cppc_write(reg_id: crate::cppc::CppcReg, value: u64) -> Self {
+        Self::CppcWrite { reg_id, value }
+    }
+}
+
+impl UnknownCall {
+    #[inline]
+    const fn new(extension_id: usize, function_id: usize) -> Self {
+        Self {
+            extension_id,
+            function_id,
+        }
+    }
+}
+
+/// Joins a 64-bit value passed in one register, or in two registers on RV32.
+///
+/// On RV32, `lo` holds the lower 32 bits and `hi` the upper 32 bits. On RV64, the value is
+/// held in `lo` alone and `hi` is ignored. Both halves are zero-extended, never sign-extended.
+///
+/// This applies to `uint64_t` parameters such as `stime_value` of `SET_TIMER`, and to
+/// the values returned by paired calls such as `COUNTER_FW_READ` and `COUNTER_FW_READ_HI`,
+/// or CPPC `READ` and `READ_HI`.
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::binary::join_u64;
+/// # #[cfg(target_pointer_width = "64")]
+/// assert_eq!(join_u64(0x1234_5678_9abc_def0, 0), 0x1234_5678_9abc_def0);
+/// # #[cfg(target_pointer_width = "32")]
+/// assert_eq!(join_u64(0x9abc_def0, 0x1234_5678), 0x1234_5678_9abc_def0);
+/// ```
+#[inline]
+pub const fn join_u64(lo: usize, hi: usize) -> u64 {
+    if cfg!(target_pointer_width = "32") {
+        (lo as u32 as u64) | ((hi as u32 as u64) << 32)
+    } else {
+        lo as u64
+    }
+}
+
+/// Splits a 64-bit value into one register, or into two registers on RV32.
+///
+/// Returns `(lo, hi)`. On RV32, `lo` holds the lower 32 bits and `hi` the upper 32 bits.
+/// On RV64, `lo` holds the whole value and `hi` is always zero, which is also the value
+/// `COUNTER_FW_READ_HI` and CPPC `READ_HI` return on RV64.
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::binary::{join_u64, split_u64};
+/// let (lo, hi) = split_u64(0x1234_5678_9abc_def0);
+/// assert_eq!(join_u64(lo, hi), 0x1234_5678_9abc_def0);
+/// # #[cfg(target_pointer_width = "64")]
+/// assert_eq!(hi, 0);
+/// ```
+#[inline]
+pub const fn split_u64(value: u64) -> (usize, usize) {
+    if cfg!(target_pointer_width = "32") {
+        (value as u32 as usize, (value >> 32) as u32 as usize)
+    } else {
+        (value as usize, 0)
+    }
+}
+
+/// Joins a physical address passed as low and high parts, if it fits in 64 bits.
+///
+/// On RV64, the high part holds bits wider than `XLEN` and must be zero.
+#[inline]
+const fn join_phys_addr(lo: usize, hi: usize) -> Option<u64> {
+    if usize::BITS >= u64::BITS && hi != 0 {
+        None
+    } else {
+        Some(join_u64(lo, hi))
+    }
+}
+
 /// Hart mask structure in SBI function calls.
+///
+/// Two hart masks are equal if they select the same set of harts, so that all hart
+/// masks with `hart_mask_base` of `usize::MAX` are equal regardless of `hart_mask`.
 #[derive(Debug, Copy, Clone)]
 pub struct HartMask {
     inner: BitVector,
@@ -719,14 +2790,546 @@ impl HartMask {
         }
         hart_mask & (1 << idx) != 0
     }
+
+    /// Construct a hart mask selecting all available harts.
+    #[inline]
+    pub const fn all() -> HartMask {
+        HartMask::from_mask_base(0, usize::MAX)
+    }
+
+    /// Construct a hart mask selecting a single hart.
+    #[inline]
+    pub const fn single(hart_id: usize) -> HartMask {
+        // `hart_mask_base` of `usize::MAX` selects all harts
+        const LAST_BASE: usize = usize::MAX - (usize::BITS as usize - 1);
+        if hart_id > LAST_BASE {
+            HartMask::from_mask_base(1 << (hart_id - LAST_BASE), LAST_BASE)
+        } else {
+            HartMask::from_mask_base(1, hart_id)
+        }
+    }
+
+    /// Construct a hart mask selecting harts in `range`.
+    ///
+    /// Returns `None` if `range` contains more than `XLEN` harts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sbi_spec::binary::HartMask;
+    /// let mask = HartMask::from_range(4..8).unwrap();
+    /// assert_eq!(mask.into_inner(), (0b1111, 4));
+    /// assert_eq!(HartMask::from_range(0..usize::BITS as usize + 1), None);
+    /// ```
+    #[inline]
+    pub const fn from_range(range: Range<usize>) -> Option<HartMask> {
+        if range.start >= range.end {
+            return Some(HartMask::from_mask_base(0, 0));
+        }
+        let len = range.end - range.start;
+        if len > usize::BITS as usize {
+            return None;
+        }
+        let hart_mask = if len == usize::BITS as usize {
+            usize::MAX
+        } else {
+            (1 << len) - 1
+        };
+        Some(HartMask::from_mask_base(hart_mask, range.start))
+    }
+
+    /// Returns the union of harts selected by `self` and `other`.
+    ///
+    /// Returns `None` if both hart masks select some harts in different windows,
+    /// that is, with different `hart_mask_base`.
+    #[inline]
+    pub const fn union(self, other: HartMask) -> Option<HartMask> {
+        if self.is_all() || other.is_all() {
+            return Some(HartMask::all());
+        }
+        if other.inner.hart_mask == 0 {
+            return Some(self);
+        }
+        if self.inner.hart_mask == 0 {
+            return Some(other);
+        }
+        if self.inner.hart_mask_base != other.inner.hart_mask_base {
+            return None;
+        }
+        Some(HartMask::from_mask_base(
+            self.inner.hart_mask | other.inner.hart_mask,
+            self.inner.hart_mask_base,
+        ))
+    }
+
+    /// Returns the intersection of harts selected by `self` and `other`.
+    ///
+    /// Returns `None` if both hart masks select some harts in different windows.
+    #[inline]
+    pub const fn intersection(self, other: HartMask) -> Option<HartMask> {
+        if self.is_all() {
+            return Some(other);
+        }
+        if other.is_all() || self.inner.hart_mask == 0 {
+            return Some(self);
+        }
+        if other.inner.hart_mask == 0 {
+            return Some(other);
+        }
+        if self.inner.hart_mask_base != other.inner.hart_mask_base {
+            return None;
+        }
+        Some(HartMask::from_mask_base(
+            self.inner.hart_mask & other.inner.hart_mask,
+            self.inner.hart_mask_base,
+        ))
+    }
+
+    /// Returns harts selected by `self` but not by `other`.
+    ///
+    /// Returns `None` if both hart masks select some harts in different windows,
+    /// or if the difference cannot be represented when `self` selects all harts.
+    #[inline]
+    pub const fn difference(self, other: HartMask) -> Option<HartMask> {
+        if other.is_all() {
+            return Some(HartMask::from_mask_base(0, 0));
+        }
+        if other.inner.hart_mask == 0 {
+            return Some(self);
+        }
+        if self.is_all() {
+            return None;
+        }
+        if self.inner.hart_mask == 0 {
+            return Some(self);
+        }
+        if self.inner.hart_mask_base != other.inner.hart_mask_base {
+            return None;
+        }
+        Some(HartMask::from_mask_base(
+            self.inner.hart_mask & !other.inner.hart_mask,
+            self.inner.hart_mask_base,
+        ))
+    }
+
+    /// Returns `true` if this hart mask selects no harts.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        !self.is_all() && self.inner.hart_mask == 0
+    }
+
+    // Canonical form of selected harts: whether all harts are selected, the lowest
+    // selected hart ID and the mask shifted to that hart ID.
+    #[inline]
+    const fn canonical(&self) -> (bool, usize, usize) {
+        let BitVector {
+            hart_mask,
+            hart_mask_base,
+        } = self.inner;
+        if hart_mask_base == usize::MAX {
+            return (true, 0, 0);
+        }
+        // bits beyond hart ID `usize::MAX` select no harts
+        let mut hart_mask = hart_mask;
+        let valid_bits = usize::MAX - hart_mask_base;
+        if valid_bits < usize::BITS as usize - 1 {
+            hart_mask &= (1 << (valid_bits + 1)) - 1;
+        }
+        if hart_mask == 0 {
+            return (false, 0, 0);
+        }
+        let shift = hart_mask.trailing_zeros();
+        (false, hart_mask_base + shift as usize, hart_mask >> shift)
+    }
+
+    /// Returns `true` if this hart mask selects all available harts.
+    #[inline]
+    pub const fn is_all(&self) -> bool {
+        self.inner.hart_mask_base == usize::MAX
+    }
+
+    /// Returns an iterator over selected hart IDs in ascending order.
+    ///
+    /// `hart_count` is the number of harts on the platform. It is only used if this
+    /// hart mask selects all harts, where the iterator yields hart IDs from `0` to
+    /// `hart_count - 1`. Otherwise, only set bits of `hart_mask` are visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sbi_spec::binary::HartMask;
+    /// let mask = HartMask::from_mask_base(0b1001, 4);
+    /// assert!(mask.iter(8).eq([4, 7]));
+    /// let mask = HartMask::from_mask_base(0, usize::MAX);
+    /// assert!(mask.iter(3).eq([0, 1, 2]));
+    /// ```
+    #[inline]
+    pub const fn iter(&self, hart_count: usize) -> HartMaskIter {
+        let BitVector {
+            hart_mask,
+            hart_mask_base,
+        } = self.inner;
+        HartMaskIter {
+            inner: if hart_mask_base == usize::MAX {
+                IterInner::All {
+                    next: 0,
+                    end: hart_count,
+                }
+            } else {
+                IterInner::Window {
+                    mask: hart_mask,
+                    base: hart_mask_base,
+                }
+            },
+        }
+    }
+
+    /// Returns an iterator over selected hart IDs, getting the number of harts from
+    /// `hart_count` only if this hart mask selects all harts.
+    #[inline]
+    pub fn iter_with(&self, hart_count: impl FnOnce() -> usize) -> HartMaskIter {
+        if self.is_all() {
+            self.iter(hart_count())
+        } else {
+            self.iter(0)
+        }
+    }
 }
 
-#[derive(Debug, Copy, Clone)]
+impl PartialEq for HartMask {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical() == other.canonical()
+    }
+}
+
+impl Eq for HartMask {}
+
+impl core::hash::Hash for HartMask {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.canonical().hash(state);
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 struct BitVector {
     hart_mask: usize,
     hart_mask_base: usize,
 }
 
+/// Splits a set of hart IDs into the fewest [`HartMask`] windows covering it.
+///
+/// Each window covers `XLEN` consecutive hart IDs starting from the smallest hart ID
+/// not yet covered. Hart IDs may be given in any order and may repeat; the iterator of
+/// hart IDs is cloned and traversed once for every window, so no allocation is needed.
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::binary::{HartMask, HartMaskSplit};
+/// let mut masks = HartMaskSplit::new([4097, 200, 0, 3]);
+/// assert_eq!(masks.next(), Some(HartMask::from_mask_base(0b1001, 0)));
+/// assert_eq!(masks.next(), Some(HartMask::from_mask_base(0b1, 200)));
+/// assert_eq!(masks.next(), Some(HartMask::from_mask_base(0b1, 4097)));
+/// assert_eq!(masks.next(), None);
+/// // all harts on a 4-hart platform are selected
+/// let mut masks = HartMaskSplit::with_hart_count([3, 1, 0, 2], 4);
+/// assert_eq!(masks.next(), Some(HartMask::from_mask_base(0, usize::MAX)));
+/// assert_eq!(masks.next(), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HartMaskSplit<I> {
+    hart_ids: I,
+    // lower bound of hart IDs not yet covered, or `None` if all windows are yielded
+    start: Option<usize>,
+    all: bool,
+}
+
+impl<I: Iterator<Item = usize> + Clone> HartMaskSplit<I> {
+    /// Creates an iterator of hart mask windows covering `hart_ids`.
+    #[inline]
+    pub fn new(hart_ids: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            hart_ids: hart_ids.into_iter(),
+            start: Some(0),
+            all: false,
+        }
+    }
+
+    /// Creates an iterator of hart mask windows covering `hart_ids` on a platform
+    /// with `hart_count` harts.
+    ///
+    /// If `hart_ids` contains exactly all hart IDs from `0` to `hart_count - 1`, the
+    /// iterator yields a single hart mask selecting all harts.
+    pub fn with_hart_count(hart_ids: impl IntoIterator<IntoIter = I>, hart_count: usize) -> Self {
+        let mut ans = Self::new(hart_ids);
+        let mut selected = 0usize;
+        for mask in ans.clone() {
+            let (hart_mask, hart_mask_base) = mask.into_inner();
+            let highest = usize::BITS - 1 - hart_mask.leading_zeros();
+            if hart_mask_base.saturating_add(highest as usize) >= hart_count {
+                return ans;
+            }
+            selected += hart_mask.count_ones() as usize;
+        }
+        ans.all = hart_count != 0 && selected == hart_count;
+        ans
+    }
+}
+
+impl<I: Iterator<Item = usize> + Clone> Iterator for HartMaskSplit<I> {
+    type Item = HartMask;
+
+    fn next(&mut self) -> Option<HartMask> {
+        if self.all {
+            self.all = false;
+            self.start = None;
+            return Some(HartMask::from_mask_base(0, usize::MAX));
+        }
+        let start = self.start?;
+        let Some(min) = self.hart_ids.clone().filter(|&id| id >= start).min() else {
+            self.start = None;
+            return None;
+        };
+        // `hart_mask_base` of `usize::MAX` would select all harts; the last window
+        // must start early enough to cover hart ID `usize::MAX`.
+        let base = min.min(usize::MAX - (usize::BITS as usize - 1));
+        let mut mask = 0;
+        for id in self.hart_ids.clone() {
+            if id >= min && id - base < usize::BITS as usize {
+                mask |= 1 << (id - base);
+            }
+        }
+        self.start = base.checked_add(usize::BITS as usize);
+        Some(HartMask::from_mask_base(mask, base))
+    }
+}
+
+/// Iterator over hart IDs selected by a [`HartMask`].
+#[derive(Clone, Debug)]
+pub struct HartMaskIter {
+    inner: IterInner,
+}
+
+#[derive(Clone, Debug)]
+enum IterInner {
+    Window { mask: usize, base: usize },
+    All { next: usize, end: usize },
+}
+
+impl Iterator for HartMaskIter {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        match &mut self.inner {
+            IterInner::Window { mask, base } => {
+                if *mask == 0 {
+                    return None;
+                }
+                let idx = mask.trailing_zeros() as usize;
+                *mask &= *mask - 1;
+                let hart_id = base.checked_add(idx);
+                if hart_id.is_none() {
+                    // remaining hart IDs overflow `usize`
+                    *mask = 0;
+                }
+                hart_id
+            }
+            IterInner::All { next, end } => {
+                if *next >= *end {
+                    return None;
+                }
+                *next += 1;
+                Some(*next - 1)
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.inner {
+            IterInner::Window { mask, .. } => (0, Some(mask.count_ones() as usize)),
+            IterInner::All { next, end } => {
+                let len = end.saturating_sub(*next);
+                (len, Some(len))
+            }
+        }
+    }
+}
+
+/// Set of pending harts that can be updated concurrently by multiple harts.
+///
+/// Pending harts are stored as bits in `WORDS` atomic `usize` words, so that hart IDs
+/// up to `WORDS * usize::BITS - 1` can be stored in this set. The number of harts on
+/// the platform may be limited further with [`with_hart_count`](Self::with_hart_count).
+/// SBI implementations usually keep one pending set for IPI or remote fence requests:
+/// the requesting hart applies the `HartMask` of the request, and each target hart
+/// tests and clears its own bit.
+///
+/// Setting bits uses `Release` ordering and taking a bit uses `Acquire` ordering,
+/// so request data written before applying a hart mask is visible to the target hart
+/// after it takes its bit.
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::binary::{AtomicHartSet, HartMask};
+/// static PENDING_IPI: AtomicHartSet<1> = AtomicHartSet::with_hart_count(8);
+///
+/// PENDING_IPI.apply(HartMask::from_mask_base(0b101, 2)).unwrap();
+/// assert!(PENDING_IPI.iter().eq([2, 4]));
+/// // on hart 4
+/// assert!(PENDING_IPI.take(4));
+/// assert!(!PENDING_IPI.take(4));
+/// ```
+#[cfg(target_has_atomic = "ptr")]
+pub struct AtomicHartSet<const WORDS: usize> {
+    pending: [AtomicUsize; WORDS],
+    hart_count: usize,
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<const WORDS: usize> AtomicHartSet<WORDS> {
+    const WORD_BITS: usize = usize::BITS as usize;
+    #[allow(clippy::declare_interior_mutable_const)]
+    const EMPTY_WORD: AtomicUsize = AtomicUsize::new(0);
+
+    /// Creates an empty hart set holding up to `WORDS * usize::BITS` harts.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_hart_count(WORDS * Self::WORD_BITS)
+    }
+
+    /// Creates an empty hart set holding harts from `0` to `hart_count - 1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hart_count` is larger than `WORDS * usize::BITS`.
+    #[inline]
+    pub const fn with_hart_count(hart_count: usize) -> Self {
+        assert!(hart_count <= WORDS * Self::WORD_BITS);
+        Self {
+            pending: [Self::EMPTY_WORD; WORDS],
+            hart_count,
+        }
+    }
+
+    /// Returns the number of harts this set can hold.
+    #[inline]
+    pub const fn hart_count(&self) -> usize {
+        self.hart_count
+    }
+
+    /// Adds all harts selected by `hart_mask` into this set.
+    ///
+    /// If `hart_mask` selects all harts, all harts of this set are added. Otherwise the
+    /// `hart_mask` window is applied with one `fetch_or` on each of the at most two
+    /// words it overlaps. Returns [`Error::InvalidParam`] without changing this set if
+    /// any selected hart ID is not less than [`hart_count`](Self::hart_count).
+    pub fn apply(&self, hart_mask: HartMask) -> Result<(), Error> {
+        let (all, base, mask) = hart_mask.canonical();
+        if all {
+            for (idx, word) in self.pending.iter().enumerate() {
+                let start = idx * Self::WORD_BITS;
+                let bits = match self.hart_count.saturating_sub(start) {
+                    0 => break,
+                    n if n >= Self::WORD_BITS => usize::MAX,
+                    n => (1 << n) - 1,
+                };
+                word.fetch_or(bits, Ordering::Release);
+            }
+            return Ok(());
+        }
+        if mask == 0 {
+            return Ok(());
+        }
+        let last = (usize::BITS - 1 - mask.leading_zeros()) as usize;
+        match base.checked_add(last) {
+            Some(last) if last < self.hart_count => {}
+            _ => return Err(Error::InvalidParam),
+        }
+        let (idx, offset) = (base / Self::WORD_BITS, base % Self::WORD_BITS);
+        self.pending[idx].fetch_or(mask << offset, Ordering::Release);
+        if offset != 0 {
+            let high = mask >> (Self::WORD_BITS - offset);
+            if high != 0 {
+                self.pending[idx + 1].fetch_or(high, Ordering::Release);
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds a hart into this set, returning `true` if it was not in this set.
+    ///
+    /// Returns [`Error::InvalidParam`] if `hart_id` is not less than [`hart_count`](Self::hart_count).
+    #[inline]
+    pub fn insert(&self, hart_id: usize) -> Result<bool, Error> {
+        match self.locate(hart_id) {
+            Some((word, bit)) => Ok(word.fetch_or(bit, Ordering::AcqRel) & bit == 0),
+            None => Err(Error::InvalidParam),
+        }
+    }
+
+    /// Removes a hart from this set, returning `true` if it was in this set.
+    ///
+    /// Returns `false` if `hart_id` is not less than [`hart_count`](Self::hart_count).
+    #[inline]
+    pub fn take(&self, hart_id: usize) -> bool {
+        match self.locate(hart_id) {
+            Some((word, bit)) => word.fetch_and(!bit, Ordering::AcqRel) & bit != 0,
+            None => false,
+        }
+    }
+
+    /// Returns `true` if a hart is in this set.
+    #[inline]
+    pub fn contains(&self, hart_id: usize) -> bool {
+        match self.locate(hart_id) {
+            Some((word, bit)) => word.load(Ordering::Acquire) & bit != 0,
+            None => false,
+        }
+    }
+
+    /// Returns an iterator over harts in this set in ascending order.
+    ///
+    /// Each word is loaded once; harts inserted or taken concurrently may or may not be visited.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.pending.iter().enumerate().flat_map(|(idx, word)| {
+            let word = word.load(Ordering::Acquire);
+            (0..Self::WORD_BITS)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| idx * Self::WORD_BITS + bit)
+        })
+    }
+
+    #[inline]
+    fn locate(&self, hart_id: usize) -> Option<(&AtomicUsize, usize)> {
+        if hart_id >= self.hart_count {
+            return None;
+        }
+        let word = &self.pending[hart_id / Self::WORD_BITS];
+        Some((word, 1 << (hart_id % Self::WORD_BITS)))
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<const WORDS: usize> Default for AtomicHartSet<WORDS> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<const WORDS: usize> core::fmt::Debug for AtomicHartSet<WORDS> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
 /// Physical slice wrapper with type annotation.
 ///
 /// This struct wraps slices in RISC-V physical memory by low and high part of the
@@ -779,6 +3382,21 @@ impl<P> Physical<P> {
     pub const fn phys_addr_hi(&self) -> usize {
         self.phys_addr_hi
     }
+
+    /// Create a physical memory slice by length and 64-bit physical address.
+    ///
+    /// The address is split into low and high parts using [`split_u64`].
+    #[inline]
+    pub const fn from_phys_addr(num_bytes: usize, phys_addr: u64) -> Self {
+        let (phys_addr_lo, phys_addr_hi) = split_u64(phys_addr);
+        Self::new(num_bytes, phys_addr_lo, phys_addr_hi)
+    }
+
+    /// Returns base address of physical memory slice, or `None` if it is wider than 64 bits.
+    #[inline]
+    pub const fn phys_addr(&self) -> Option<u64> {
+        join_phys_addr(self.phys_addr_lo, self.phys_addr_hi)
+    }
 }
 
 /// Shared memory physical address raw pointer with type annotation.
@@ -860,6 +3478,22 @@ impl<T> SharedPtr<T> {
     pub const fn phys_addr_hi(self) -> usize {
         self.phys_addr_hi
     }
+
+    /// Create a shared physical memory pointer by 64-bit physical address.
+    ///
+    /// The address is split into low and high parts using [`split_u64`].
+    #[inline]
+    pub const fn from_phys_addr(phys_addr: u64) -> Self {
+        let (phys_addr_lo, phys_addr_hi) = split_u64(phys_addr);
+        Self::new(phys_addr_lo, phys_addr_hi)
+    }
+
+    /// Returns physical address of shared physical memory pointer, or `None` if it is
+    /// wider than 64 bits.
+    #[inline]
+    pub const fn phys_addr(self) -> Option<u64> {
+        join_phys_addr(self.phys_addr_lo, self.phys_addr_hi)
+    }
 }
 
 impl<T> Clone for SharedPtr<T> {
@@ -873,7 +3507,8 @@ impl<T> Copy for SharedPtr<T> {}
 
 #[cfg(test)]
 mod tests {
-    use super::HartMask;
+    use super::{join_u64, split_u64, HartMask, Physical, SbiCall, SharedPtr, UnknownCall};
+    use crate::cppc::CppcReg;
 
     #[test]
     fn rustsbi_hart_mask() {
@@ -904,4 +3539,531 @@ mod tests {
         }
         assert!(mask.has_bit(usize::MAX));
     }
+
+    #[test]
+    fn hart_mask_iter() {
+        let mask = HartMask::from_mask_base(0b110, 500);
+        assert!(mask.iter(0).eq([501, 502]));
+        let max_bit = 1 << (usize::BITS - 1);
+        let mask = HartMask::from_mask_base(max_bit | 1, 600);
+        assert!(mask.iter(0).eq([600, 600 + usize::BITS as usize - 1]));
+        assert_eq!(mask.iter(0).size_hint(), (0, Some(2)));
+        let mask = HartMask::from_mask_base(0b11, usize::MAX - 1);
+        assert!(mask.iter(0).eq([usize::MAX - 1, usize::MAX]));
+        let mask = HartMask::from_mask_base(usize::MAX, usize::MAX - 2);
+        assert!(mask
+            .iter(0)
+            .eq([usize::MAX - 2, usize::MAX - 1, usize::MAX]));
+        let mask = HartMask::from_mask_base(0, 0);
+        assert_eq!(mask.iter(4).next(), None);
+        // all harts
+        let mask = HartMask::from_mask_base(0, usize::MAX);
+        assert!(mask.is_all());
+        assert!(mask.iter(4).eq(0..4));
+        assert_eq!(mask.iter(4).size_hint(), (4, Some(4)));
+        assert!(mask.iter_with(|| 2).eq(0..2));
+        let mask = HartMask::from_mask_base(0b1, 3);
+        assert!(mask.iter_with(|| unreachable!()).eq([3]));
+    }
+
+    #[test]
+    fn hart_mask_split() {
+        use super::HartMaskSplit;
+        let xlen = usize::BITS as usize;
+        let masks = HartMaskSplit::new([0, 3, 200, 4097]);
+        assert!(masks.eq([
+            HartMask::from_mask_base(0b1001, 0),
+            HartMask::from_mask_base(0b1, 200),
+            HartMask::from_mask_base(0b1, 4097),
+        ]));
+        // XLEN boundaries
+        let masks = HartMaskSplit::new([xlen, xlen - 1, 0, xlen - 1]);
+        assert!(masks.eq([
+            HartMask::from_mask_base(1 | (1 << (xlen - 1)), 0),
+            HartMask::from_mask_base(0b1, xlen),
+        ]));
+        let masks = HartMaskSplit::new([5, 5 + xlen - 1, 5 + xlen, 5 + 2 * xlen - 1]);
+        assert!(masks.eq([
+            HartMask::from_mask_base(1 | (1 << (xlen - 1)), 5),
+            HartMask::from_mask_base(1 | (1 << (xlen - 1)), 5 + xlen),
+        ]));
+        // hart IDs near `usize::MAX`
+        let masks = HartMaskSplit::new([usize::MAX, usize::MAX - xlen]);
+        assert!(masks.eq([
+            HartMask::from_mask_base(0b1, usize::MAX - xlen),
+            HartMask::from_mask_base(1 << (xlen - 1), usize::MAX - xlen + 1),
+        ]));
+        assert_eq!(HartMaskSplit::new([]).next(), None);
+        // all harts
+        let masks = HartMaskSplit::with_hart_count(0..200, 200);
+        assert!(masks.eq([HartMask::from_mask_base(0, usize::MAX)]));
+        let masks = HartMaskSplit::with_hart_count([1, 0, 1], 2);
+        assert!(masks.eq([HartMask::from_mask_base(0, usize::MAX)]));
+        let masks = HartMaskSplit::with_hart_count([0, 1], 3);
+        assert!(masks.eq([HartMask::from_mask_base(0b11, 0)]));
+        let masks = HartMaskSplit::with_hart_count([0, 1, 2], 2);
+        assert!(masks.eq([HartMask::from_mask_base(0b111, 0)]));
+        assert_eq!(HartMaskSplit::with_hart_count([], 0).next(), None);
+    }
+
+    #[test]
+    fn hart_mask_set_ops() {
+        let xlen = usize::BITS as usize;
+        assert_eq!(HartMask::all().into_inner(), (0, usize::MAX));
+        assert_eq!(HartMask::single(5).into_inner(), (1, 5));
+        let mask = HartMask::single(usize::MAX);
+        assert!(mask.has_bit(usize::MAX) && !mask.is_all());
+        assert!(mask.iter(0).eq([usize::MAX]));
+        assert_eq!(
+            HartMask::from_range(0..xlen).unwrap().into_inner(),
+            (usize::MAX, 0)
+        );
+        assert_eq!(HartMask::from_range(0..xlen + 1), None);
+        assert!(HartMask::from_range(3..3).unwrap().is_empty());
+        // canonical equality
+        assert_eq!(
+            HartMask::from_mask_base(0b1, usize::MAX),
+            HartMask::from_mask_base(0b10, usize::MAX)
+        );
+        assert_eq!(
+            HartMask::from_mask_base(0b1100, 0),
+            HartMask::from_mask_base(0b11, 2)
+        );
+        assert_eq!(
+            HartMask::from_mask_base(0, 3),
+            HartMask::from_mask_base(0, 7)
+        );
+        assert_ne!(HartMask::from_mask_base(0, 3), HartMask::all());
+        assert_ne!(HartMask::single(3), HartMask::single(4));
+        assert_eq!(
+            HartMask::from_mask_base(0b111, usize::MAX - 1),
+            HartMask::from_mask_base(0b011, usize::MAX - 1)
+        );
+        assert_eq!(
+            HartMask::single(usize::MAX),
+            HartMask::from_mask_base(0b10, usize::MAX - 1)
+        );
+        // set operations
+        let a = HartMask::from_mask_base(0b0110, 8);
+        let b = HartMask::from_mask_base(0b1100, 8);
+        assert_eq!(a.union(b), Some(HartMask::from_mask_base(0b1110, 8)));
+        assert_eq!(a.intersection(b), Some(HartMask::single(10)));
+        assert_eq!(a.difference(b), Some(HartMask::single(9)));
+        let c = HartMask::single(100);
+        assert_eq!(a.union(c), None);
+        assert_eq!(a.intersection(c), None);
+        assert_eq!(a.difference(c), None);
+        assert_eq!(a.union(HartMask::all()), Some(HartMask::all()));
+        assert_eq!(a.intersection(HartMask::all()), Some(a));
+        assert!(a.difference(HartMask::all()).unwrap().is_empty());
+        assert_eq!(HartMask::all().difference(a), None);
+        let empty = HartMask::from_mask_base(0, 0);
+        assert_eq!(HartMask::all().difference(empty), Some(HartMask::all()));
+        assert_eq!(c.union(empty), Some(c));
+        assert_eq!(empty.union(c), Some(c));
+        assert!(c.intersection(empty).unwrap().is_empty());
+    }
+
+    #[test]
+    fn atomic_hart_set() {
+        use super::{AtomicHartSet, Error};
+        let set = AtomicHartSet::<2>::with_hart_count(70);
+        assert_eq!(set.hart_count(), 70);
+        assert_eq!(set.iter().next(), None);
+        assert_eq!(set.apply(HartMask::from_mask_base(0b11, 68)), Ok(()));
+        assert_eq!(
+            set.apply(HartMask::from_mask_base(0b111, 68)),
+            Err(Error::InvalidParam)
+        );
+        assert!(set.iter().eq([68, 69]));
+        assert_eq!(set.insert(3), Ok(true));
+        assert_eq!(set.insert(3), Ok(false));
+        assert_eq!(set.insert(70), Err(Error::InvalidParam));
+        assert!(set.contains(3) && !set.contains(4) && !set.contains(70));
+        assert!(set.take(68));
+        assert!(!set.take(68));
+        assert!(!set.take(1000));
+        assert!(set.iter().eq([3, 69]));
+        assert_eq!(set.apply(HartMask::all()), Ok(()));
+        assert!(set.iter().eq(0..70));
+        // window across two words, and the whole capacity of a set
+        let set = AtomicHartSet::<2>::new();
+        assert_eq!(set.hart_count(), 2 * usize::BITS as usize);
+        let base = usize::BITS as usize - 2;
+        assert_eq!(set.apply(HartMask::from_mask_base(0b1011, base)), Ok(()));
+        assert!(set.iter().eq([base, base + 1, base + 3]));
+        assert!(set.take(base + 3) && !set.contains(base + 3));
+        assert_eq!(set.apply(HartMask::all()), Ok(()));
+        assert_eq!(set.iter().count(), 2 * usize::BITS as usize);
+        assert_eq!(
+            set.apply(HartMask::from_mask_base(1, usize::MAX - 1)),
+            Err(Error::InvalidParam)
+        );
+        assert!(AtomicHartSet::<0>::new().apply(HartMask::all()).is_ok());
+        assert_eq!(
+            AtomicHartSet::<0>::new().apply(HartMask::single(0)),
+            Err(Error::InvalidParam)
+        );
+    }
+
+    #[test]
+    fn sbi_ret_error() {
+        use super::{Error, SbiRet};
+        assert_eq!(
+            SbiRet::invalid_state().into_result(),
+            Err(Error::InvalidState)
+        );
+        assert_eq!(SbiRet::bad_range().into_result(), Err(Error::BadRange));
+        assert_eq!(SbiRet::timeout().into_result(), Err(Error::Timeout));
+        assert_eq!(SbiRet::io().into_result(), Err(Error::Io));
+        assert_eq!(
+            SbiRet::denied_locked().into_result(),
+            Err(Error::DeniedLocked)
+        );
+        let ret = SbiRet {
+            error: -15isize as _,
+            value: 0,
+        };
+        assert_eq!(ret.into_result(), Err(Error::Custom(-15)));
+        assert_eq!(SbiRet::from(Error::Custom(-15)), ret);
+        for ret in [
+            SbiRet::failed(),
+            SbiRet::not_supported(),
+            SbiRet::invalid_param(),
+            SbiRet::denied(),
+            SbiRet::invalid_address(),
+            SbiRet::already_available(),
+            SbiRet::already_started(),
+            SbiRet::already_stopped(),
+            SbiRet::no_shmem(),
+            SbiRet::invalid_state(),
+            SbiRet::bad_range(),
+            SbiRet::timeout(),
+            SbiRet::io(),
+            SbiRet::denied_locked(),
+        ] {
+            assert_eq!(SbiRet::from(ret.unwrap_err()), ret);
+        }
+    }
+
+    #[test]
+    fn sbi_call_decode() {
+        use crate::{base, dbcn, hsm, pmu, rfnc, spi};
+        let call = SbiCall::decode([
+            dbcn::EID_DBCN,
+            0,
+            0,
+            0,
+            0,
+            0,
+            base::PROBE_EXTENSION,
+            base::EID_BASE,
+        ]);
+        assert_eq!(
+            call,
+            Ok(SbiCall::BaseProbeExtension {
+                extension_id: dbcn::EID_DBCN
+            })
+        );
+        let call = SbiCall::decode([2, 0x8000_0000, 42, 0, 0, 0, hsm::HART_START, hsm::EID_HSM]);
+        assert_eq!(
+            call,
+            Ok(SbiCall::HsmHartStart {
+                hartid: 2,
+                start_addr: 0x8000_0000,
+                opaque: 42
+            })
+        );
+        let call = SbiCall::decode([0b101, 64, 0, 0, 0, 0, spi::SEND_IPI, spi::EID_SPI]);
+        assert_eq!(
+            call,
+            Ok(SbiCall::SpiSendIpi {
+                hart_mask: HartMask::from_mask_base(0b101, 64)
+            })
+        );
+        let call = SbiCall::decode([
+            0b11,
+            0,
+            0x1000,
+            0x2000,
+            7,
+            0,
+            rfnc::REMOTE_SFENCE_VMA_ASID,
+            rfnc::EID_RFNC,
+        ]);
+        assert_eq!(
+            call,
+            Ok(SbiCall::RfncRemoteSfenceVmaAsid {
+                hart_mask: HartMask::from_mask_base(0b11, 0),
+                start: 0x1000,
+                size: 0x2000,
+                asid: 7
+            })
+        );
+        let call = SbiCall::decode([
+            3,
+            0b1,
+            0,
+            0x1_0002,
+            0x55,
+            0,
+            pmu::COUNTER_CONFIG_MATCHING,
+            pmu::EID_PMU,
+        ]);
+        assert_eq!(
+            call,
+            Ok(SbiCall::PmuCounterConfigMatching {
+                counter_mask: pmu::CounterMask::from_mask_base(0b1, 3),
+                config_flags: pmu::ConfigFlags::empty(),
+                event_idx: 0x1_0002,
+                event_data: 0x55
+            })
+        );
+        // unknown function in a standard extension
+        assert_eq!(
+            SbiCall::decode([0, 0, 0, 0, 0, 0, 7, hsm::EID_HSM]),
+            Err(UnknownCall {
+                extension_id: hsm::EID_HSM,
+                function_id: 7
+            })
+        );
+        // unknown extension
+        assert_eq!(
+            SbiCall::decode([0, 0, 0, 0, 0, 0, 0, 0x0A00_0000]),
+            Err(UnknownCall {
+                extension_id: 0x0A00_0000,
+                function_id: 0
+            })
+        );
+    }
+
+    #[test]
+    fn sbi_call_encode() {
+        use crate::{
+            base, cppc, dbcn, dbtr, fwft, hsm, mpxy, nacl, pmu, rfnc, spi, srst, sse, sta, susp,
+            time,
+        };
+        // hart_mask goes to a0 and hart_mask_base goes to a1
+        let call = SbiCall::SpiSendIpi {
+            hart_mask: HartMask::from_mask_base(0b1001, 128),
+        };
+        assert_eq!(
+            call.encode(),
+            [0b1001, 128, 0, 0, 0, 0, spi::SEND_IPI, spi::EID_SPI]
+        );
+        let call = SbiCall::SrstSystemReset {
+            reset_type: srst::ResetType::WarmReboot,
+            reset_reason: srst::ResetReason::SystemFailure,
+        };
+        assert_eq!(
+            call.encode(),
+            [2, 1, 0, 0, 0, 0, srst::SYSTEM_RESET, srst::EID_SRST]
+        );
+        // every standard function survives a decode and encode round trip
+        let x = 0x1234;
+        let y = 0x5678;
+        let z = 0x9abc;
+        let w = 0xdef0;
+        let v = 0x2468;
+        let regs_list = [
+            [0, 0, 0, 0, 0, 0, base::GET_SBI_SPEC_VERSION, base::EID_BASE],
+            [0, 0, 0, 0, 0, 0, base::GET_SBI_IMPL_ID, base::EID_BASE],
+            [0, 0, 0, 0, 0, 0, base::GET_SBI_IMPL_VERSION, base::EID_BASE],
+            [x, 0, 0, 0, 0, 0, base::PROBE_EXTENSION, base::EID_BASE],
+            [0, 0, 0, 0, 0, 0, base::GET_MVENDORID, base::EID_BASE],
+            [0, 0, 0, 0, 0, 0, base::GET_MARCHID, base::EID_BASE],
+            [0, 0, 0, 0, 0, 0, base::GET_MIMPID, base::EID_BASE],
+            [x, 0, 0, 0, 0, 0, time::SET_TIMER, time::EID_TIME],
+            [x, y, 0, 0, 0, 0, spi::SEND_IPI, spi::EID_SPI],
+            [x, y, 0, 0, 0, 0, rfnc::REMOTE_FENCE_I, rfnc::EID_RFNC],
+            [x, y, z, w, 0, 0, rfnc::REMOTE_SFENCE_VMA, rfnc::EID_RFNC],
+            [
+                x,
+                y,
+                z,
+                w,
+                v,
+                0,
+                rfnc::REMOTE_SFENCE_VMA_ASID,
+                rfnc::EID_RFNC,
+            ],
+            [
+                x,
+                y,
+                z,
+                w,
+                v,
+                0,
+                rfnc::REMOTE_HFENCE_GVMA_VMID,
+                rfnc::EID_RFNC,
+            ],
+            [x, y, z, w, 0, 0, rfnc::REMOTE_HFENCE_GVMA, rfnc::EID_RFNC],
+            [
+                x,
+                y,
+                z,
+                w,
+                v,
+                0,
+                rfnc::REMOTE_HFENCE_VVMA_ASID,
+                rfnc::EID_RFNC,
+            ],
+            [x, y, z, w, 0, 0, rfnc::REMOTE_HFENCE_VVMA, rfnc::EID_RFNC],
+            [x, y, z, 0, 0, 0, hsm::HART_START, hsm::EID_HSM],
+            [0, 0, 0, 0, 0, 0, hsm::HART_STOP, hsm::EID_HSM],
+            [x, 0, 0, 0, 0, 0, hsm::HART_GET_STATUS, hsm::EID_HSM],
+            [x, y, z, 0, 0, 0, hsm::HART_SUSPEND, hsm::EID_HSM],
+            [x, y, 0, 0, 0, 0, srst::SYSTEM_RESET, srst::EID_SRST],
+            [0, 0, 0, 0, 0, 0, pmu::NUM_COUNTERS, pmu::EID_PMU],
+            [x, 0, 0, 0, 0, 0, pmu::COUNTER_GET_INFO, pmu::EID_PMU],
+            [x, y, z, w, v, 0, pmu::COUNTER_CONFIG_MATCHING, pmu::EID_PMU],
+            [x, y, z, w, 0, 0, pmu::COUNTER_START, pmu::EID_PMU],
+            [x, y, z, 0, 0, 0, pmu::COUNTER_STOP, pmu::EID_PMU],
+            [x, 0, 0, 0, 0, 0, pmu::COUNTER_FW_READ, pmu::EID_PMU],
+            [x, 0, 0, 0, 0, 0, pmu::COUNTER_FW_READ_HI, pmu::EID_PMU],
+            [x, y, z, 0, 0, 0, pmu::SNAPSHOT_SET_SHMEM, pmu::EID_PMU],
+            [x, y, z, 0, 0, 0, dbcn::CONSOLE_WRITE, dbcn::EID_DBCN],
+            [x, y, z, 0, 0, 0, dbcn::CONSOLE_READ, dbcn::EID_DBCN],
+            [
+                0x42,
+                0,
+                0,
+                0,
+                0,
+                0,
+                dbcn::CONSOLE_WRITE_BYTE,
+                dbcn::EID_DBCN,
+            ],
+            [x, y, z, 0, 0, 0, susp::SUSPEND, susp::EID_SUSP],
+            [x, 0, 0, 0, 0, 0, cppc::PROBE, cppc::EID_CPPC],
+            [x, 0, 0, 0, 0, 0, cppc::READ, cppc::EID_CPPC],
+            [x, 0, 0, 0, 0, 0, cppc::READ_HI, cppc::EID_CPPC],
+            [x, y, 0, 0, 0, 0, cppc::WRITE, cppc::EID_CPPC],
+            [x, 0, 0, 0, 0, 0, nacl::PROBE_FEATURE, nacl::EID_NACL],
+            [x, y, z, 0, 0, 0, nacl::SET_SHMEM, nacl::EID_NACL],
+            [x, 0, 0, 0, 0, 0, nacl::SYNC_CSR, nacl::EID_NACL],
+            [x, 0, 0, 0, 0, 0, nacl::SYNC_HFENCE, nacl::EID_NACL],
+            [0, 0, 0, 0, 0, 0, nacl::SYNC_SRET, nacl::EID_NACL],
+            [x, y, z, 0, 0, 0, sta::SET_SHMEM, sta::EID_STA],
+            [x, y, z, w, v, 0, sse::READ_ATTRS, sse::EID_SSE],
+            [x, y, z, w, v, 0, sse::WRITE_ATTRS, sse::EID_SSE],
+            [x, y, z, 0, 0, 0, sse::REGISTER, sse::EID_SSE],
+            [x, 0, 0, 0, 0, 0, sse::UNREGISTER, sse::EID_SSE],
+            [x, 0, 0, 0, 0, 0, sse::ENABLE, sse::EID_SSE],
+            [x, 0, 0, 0, 0, 0, sse::DISABLE, sse::EID_SSE],
+            [0, 0, 0, 0, 0, 0, sse::COMPLETE, sse::EID_SSE],
+            [x, y, 0, 0, 0, 0, sse::INJECT, sse::EID_SSE],
+            [0, 0, 0, 0, 0, 0, sse::HART_UNMASK, sse::EID_SSE],
+            [0, 0, 0, 0, 0, 0, sse::HART_MASK, sse::EID_SSE],
+            [x, 1, 1, 0, 0, 0, fwft::SET, fwft::EID_FWFT],
+            [x, 0, 0, 0, 0, 0, fwft::GET, fwft::EID_FWFT],
+            [x, 0, 0, 0, 0, 0, dbtr::NUM_TRIGGERS, dbtr::EID_DBTR],
+            [x, y, z, 0, 0, 0, dbtr::SET_SHMEM, dbtr::EID_DBTR],
+            [x, y, 0, 0, 0, 0, dbtr::READ_TRIGGERS, dbtr::EID_DBTR],
+            [x, 0, 0, 0, 0, 0, dbtr::INSTALL_TRIGGERS, dbtr::EID_DBTR],
+            [x, 0, 0, 0, 0, 0, dbtr::UPDATE_TRIGGERS, dbtr::EID_DBTR],
+            [x, y, 0, 0, 0, 0, dbtr::UNINSTALL_TRIGGERS, dbtr::EID_DBTR],
+            [x, y, 0, 0, 0, 0, dbtr::ENABLE_TRIGGERS, dbtr::EID_DBTR],
+            [x, y, 0, 0, 0, 0, dbtr::DISABLE_TRIGGERS, dbtr::EID_DBTR],
+            [0, 0, 0, 0, 0, 0, mpxy::GET_SHMEM_SIZE, mpxy::EID_MPXY],
+            [x, y, z, 0, 0, 0, mpxy::SET_SHMEM, mpxy::EID_MPXY],
+            [x, 0, 0, 0, 0, 0, mpxy::GET_CHANNEL_IDS, mpxy::EID_MPXY],
+            [x, 1, 2, 0, 0, 0, mpxy::READ_ATTRIBUTES, mpxy::EID_MPXY],
+            [x, 1, 2, 0, 0, 0, mpxy::WRITE_ATTRIBUTES, mpxy::EID_MPXY],
+            [
+                x,
+                1,
+                y,
+                0,
+                0,
+                0,
+                mpxy::SEND_MESSAGE_WITH_RESPONSE,
+                mpxy::EID_MPXY,
+            ],
+            [
+                x,
+                1,
+                y,
+                0,
+                0,
+                0,
+                mpxy::SEND_MESSAGE_WITHOUT_RESPONSE,
+                mpxy::EID_MPXY,
+            ],
+            [
+                x,
+                0,
+                0,
+                0,
+                0,
+                0,
+                mpxy::GET_NOTIFICATION_EVENTS,
+                mpxy::EID_MPXY,
+            ],
+        ];
+        for regs in regs_list {
+            let call = SbiCall::decode(regs).unwrap();
+            assert_eq!(call.encode(), regs, "{call:?}");
+            assert_eq!(SbiCall::decode(call.encode()), Ok(call));
+        }
+    }
+
+    #[test]
+    fn split_join_u64() {
+        for value in [0, 1, 0x8000_0000, 0xffff_ffff, 0x1_0000_0000, u64::MAX] {
+            let (lo, hi) = split_u64(value);
+            assert_eq!(join_u64(lo, hi), value);
+            if usize::BITS == 64 {
+                assert_eq!(hi, 0);
+            }
+        }
+        let call = SbiCall::time_set_timer(0x8000_0000_8000_0000);
+        assert_eq!(SbiCall::decode(call.encode()), Ok(call));
+        let call = SbiCall::cppc_write(CppcReg::DesiredPerformance, u64::MAX);
+        assert_eq!(SbiCall::decode(call.encode()), Ok(call));
+        let (_, hi) = SbiCall::cppc_read(CppcReg::DeliveredPerformanceCounter);
+        assert_eq!(hi.is_some(), usize::BITS == 32);
+        let (_, hi) = SbiCall::pmu_counter_fw_read(0);
+        assert_eq!(hi.is_some(), usize::BITS == 32);
+
+        let ptr = SharedPtr::<[u8; 64]>::from_phys_addr(0x8000_1000);
+        assert_eq!(ptr.phys_addr(), Some(0x8000_1000));
+        assert_eq!((ptr.phys_addr_lo(), ptr.phys_addr_hi()), (0x8000_1000, 0));
+        let slice = Physical::<&[u8]>::from_phys_addr(16, u64::MAX);
+        assert_eq!((slice.num_bytes(), slice.phys_addr()), (16, Some(u64::MAX)));
+        if usize::BITS == 64 {
+            assert_eq!(SharedPtr::<u8>::new(0, 1).phys_addr(), None);
+        }
+    }
+
+    #[cfg(feature = "legacy")]
+    #[test]
+    fn sbi_call_legacy() {
+        use crate::legacy::*;
+        let regs_list = [
+            [0x1234, 0, 0, 0, 0, 0, 0, LEGACY_SET_TIMER],
+            [0x41, 0, 0, 0, 0, 0, 0, LEGACY_CONSOLE_PUTCHAR],
+            [0, 0, 0, 0, 0, 0, 0, LEGACY_CONSOLE_GETCHAR],
+            [0, 0, 0, 0, 0, 0, 0, LEGACY_CLEAR_IPI],
+            [0x1000, 0, 0, 0, 0, 0, 0, LEGACY_SEND_IPI],
+            [0x1000, 0, 0, 0, 0, 0, 0, LEGACY_REMOTE_FENCE_I],
+            [0x1000, 0x2000, 0x3000, 0, 0, 0, 0, LEGACY_REMOTE_SFENCE_VMA],
+            [
+                0x1000,
+                0x2000,
+                0x3000,
+                4,
+                0,
+                0,
+                0,
+                LEGACY_REMOTE_SFENCE_VMA_ASID,
+            ],
+            [0, 0, 0, 0, 0, 0, 0, LEGACY_SHUTDOWN],
+        ];
+        for regs in regs_list {
+            let call = SbiCall::decode(regs).unwrap();
+            assert_eq!(call.encode(), regs, "{call:?}");
+        }
+        assert!(SbiCall::decode([0, 0, 0, 0, 0, 0, 0, 0x09]).is_err());
+    }
 }
@@ -4,6 +4,8 @@
 pub const EID_CPPC: usize = crate::eid_from_str("CPPC") as _;
 pub use fid::*;
 
+use crate::binary::Error;
+
 /// Declared in §14.
 mod fid {
     /// Function ID to probe a CPPC register.
@@ -23,3 +25,392 @@ mod fid {
     /// Declared in §14.4.
     pub const WRITE: usize = 3;
 }
+
+/// CPPC register IDs.
+///
+/// Register IDs not listed here are reserved.
+///
+/// Declared in §14.
+pub mod reg_id {
+    /// Highest performance.
+    pub const HIGHEST_PERFORMANCE: u32 = 0x0000_0000;
+    /// Nominal performance.
+    pub const NOMINAL_PERFORMANCE: u32 = 0x0000_0001;
+    /// Lowest nonlinear performance.
+    pub const LOWEST_NONLINEAR_PERFORMANCE: u32 = 0x0000_0002;
+    /// Lowest performance.
+    pub const LOWEST_PERFORMANCE: u32 = 0x0000_0003;
+    /// Guaranteed performance register.
+    pub const GUARANTEED_PERFORMANCE: u32 = 0x0000_0004;
+    /// Desired performance register.
+    pub const DESIRED_PERFORMANCE: u32 = 0x0000_0005;
+    /// Minimum performance register.
+    pub const MINIMUM_PERFORMANCE: u32 = 0x0000_0006;
+    /// Maximum performance register.
+    pub const MAXIMUM_PERFORMANCE: u32 = 0x0000_0007;
+    /// Performance reduction tolerance register.
+    pub const PERFORMANCE_REDUCTION_TOLERANCE: u32 = 0x0000_0008;
+    /// Time window register.
+    pub const TIME_WINDOW: u32 = 0x0000_0009;
+    /// Counter wraparound time.
+    pub const COUNTER_WRAPAROUND_TIME: u32 = 0x0000_000A;
+    /// Reference performance counter register.
+    pub const REFERENCE_PERFORMANCE_COUNTER: u32 = 0x0000_000B;
+    /// Delivered performance counter register.
+    pub const DELIVERED_PERFORMANCE_COUNTER: u32 = 0x0000_000C;
+    /// Performance limited register.
+    pub const PERFORMANCE_LIMITED: u32 = 0x0000_000D;
+    /// CPPC enable register.
+    pub const CPPC_ENABLE: u32 = 0x0000_000E;
+    /// Autonomous selection enable.
+    pub const AUTONOMOUS_SELECTION_ENABLE: u32 = 0x0000_000F;
+    /// Autonomous activity window register.
+    pub const AUTONOMOUS_ACTIVITY_WINDOW: u32 = 0x0000_0010;
+    /// Energy performance preference register.
+    pub const ENERGY_PERFORMANCE_PREFERENCE: u32 = 0x0000_0011;
+    /// Reference performance.
+    pub const REFERENCE_PERFORMANCE: u32 = 0x0000_0012;
+    /// Lowest frequency.
+    pub const LOWEST_FREQUENCY: u32 = 0x0000_0013;
+    /// Nominal frequency.
+    pub const NOMINAL_FREQUENCY: u32 = 0x0000_0014;
+    /// Transition latency.
+    pub const TRANSITION_LATENCY: u32 = 0x8000_0000;
+}
+
+/// Access permission of a CPPC register.
+///
+/// Declared in §14.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RegAccess {
+    /// The register can only be read; writing it is denied.
+    ReadOnly,
+    /// The register can only be written; reading it is denied.
+    ///
+    /// No standard CPPC register is write-only at present.
+    WriteOnly,
+    /// The register can be read and written.
+    ReadWrite,
+}
+
+/// Width of a CPPC register.
+///
+/// Declared in §14.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RegWidth {
+    /// The register has a fixed width in bits.
+    Fixed(u32),
+    /// The register width is defined by the platform, up to `max` bits.
+    Platform {
+        /// Largest width in bits the platform may implement.
+        max: u32,
+    },
+}
+
+impl RegWidth {
+    /// Returns the largest width of this register in bits.
+    #[inline]
+    pub const fn max(self) -> u32 {
+        match self {
+            Self::Fixed(bits) | Self::Platform { max: bits } => bits,
+        }
+    }
+
+    /// Returns the width of this register in bits given the platform counter width.
+    ///
+    /// Returns `None` if `platform_bits` is not a valid width for a platform defined register.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sbi_spec::cppc::RegWidth;
+    /// assert_eq!(RegWidth::Fixed(32).resolve(64), Some(32));
+    /// assert_eq!(RegWidth::Platform { max: 64 }.resolve(32), Some(32));
+    /// assert_eq!(RegWidth::Platform { max: 64 }.resolve(48), None);
+    /// ```
+    #[inline]
+    pub const fn resolve(self, platform_bits: u32) -> Option<u32> {
+        match self {
+            Self::Fixed(bits) => Some(bits),
+            Self::Platform { max } if platform_bits == 32 || platform_bits == max => {
+                Some(platform_bits)
+            }
+            Self::Platform { .. } => None,
+        }
+    }
+}
+
+/// CPPC register.
+///
+/// | Register ID | Register | Width | Access |
+/// |:------------|:---------|:------|:-------|
+/// | `0x0000_0000` | HighestPerformance | 32 | RO |
+/// | `0x0000_0001` | NominalPerformance | 32 | RO |
+/// | `0x0000_0002` | LowestNonlinearPerformance | 32 | RO |
+/// | `0x0000_0003` | LowestPerformance | 32 | RO |
+/// | `0x0000_0004` | GuaranteedPerformance | 32 | RO |
+/// | `0x0000_0005` | DesiredPerformance | 32 | RW |
+/// | `0x0000_0006` | MinimumPerformance | 32 | RW |
+/// | `0x0000_0007` | MaximumPerformance | 32 | RW |
+/// | `0x0000_0008` | PerformanceReductionTolerance | 32 | RW |
+/// | `0x0000_0009` | TimeWindow | 32 | RW |
+/// | `0x0000_000A` | CounterWraparoundTime | 32 or 64 (platform) | RO |
+/// | `0x0000_000B` | ReferencePerformanceCounter | 32 or 64 (platform) | RO |
+/// | `0x0000_000C` | DeliveredPerformanceCounter | 32 or 64 (platform) | RO |
+/// | `0x0000_000D` | PerformanceLimited | 32 | RW |
+/// | `0x0000_000E` | CppcEnable | 32 | RW |
+/// | `0x0000_000F` | AutonomousSelectionEnable | 32 | RW |
+/// | `0x0000_0010` | AutonomousActivityWindow | 32 | RW |
+/// | `0x0000_0011` | EnergyPerformancePreference | 32 | RW |
+/// | `0x0000_0012` | ReferencePerformance | 32 | RO |
+/// | `0x0000_0013` | LowestFrequency | 32 | RO |
+/// | `0x0000_0014` | NominalFrequency | 32 | RO |
+/// | `0x8000_0000` | TransitionLatency | 32 | RO |
+///
+/// Register IDs `0x0000_0015..=0x7FFF_FFFF` and `0x8000_0001..=0xFFFF_FFFF` are reserved.
+/// The SBI specification does not allocate register IDs for ACPI Functional Fixed
+/// Hardware (FFH); an ACPI FFH register address is mapped onto the register IDs above.
+///
+/// Declared in §14.
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::{binary::Error, cppc::{CppcReg, RegAccess, RegWidth}};
+/// let reg = CppcReg::from_raw(0x5);
+/// assert_eq!(reg, CppcReg::DesiredPerformance);
+/// assert_eq!((reg.width(), reg.access()), (Some(RegWidth::Fixed(32)), Some(RegAccess::ReadWrite)));
+/// assert_eq!(CppcReg::NominalPerformance.check_write(), Err(Error::Denied));
+/// assert_eq!(CppcReg::from_raw(0x15).check_read(), Err(Error::InvalidParam));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CppcReg {
+    /// Highest performance.
+    HighestPerformance,
+    /// Nominal performance.
+    NominalPerformance,
+    /// Lowest nonlinear performance.
+    LowestNonlinearPerformance,
+    /// Lowest performance.
+    LowestPerformance,
+    /// Guaranteed performance register.
+    GuaranteedPerformance,
+    /// Desired performance register.
+    DesiredPerformance,
+    /// Minimum performance register.
+    MinimumPerformance,
+    /// Maximum performance register.
+    MaximumPerformance,
+    /// Performance reduction tolerance register.
+    PerformanceReductionTolerance,
+    /// Time window register.
+    TimeWindow,
+    /// Counter wraparound time.
+    CounterWraparoundTime,
+    /// Reference performance counter register.
+    ReferencePerformanceCounter,
+    /// Delivered performance counter register.
+    DeliveredPerformanceCounter,
+    /// Performance limited register.
+    PerformanceLimited,
+    /// CPPC enable register.
+    CppcEnable,
+    /// Autonomous selection enable.
+    AutonomousSelectionEnable,
+    /// Autonomous activity window register.
+    AutonomousActivityWindow,
+    /// Energy performance preference register.
+    EnergyPerformancePreference,
+    /// Reference performance.
+    ReferencePerformance,
+    /// Lowest frequency.
+    LowestFrequency,
+    /// Nominal frequency.
+    NominalFrequency,
+    /// Transition latency.
+    TransitionLatency,
+    /// Reserved register ID for future use.
+    Reserved(u32),
+}
+
+impl CppcReg {
+    /// Converts a raw register ID into `CppcReg`.
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        match raw {
+            reg_id::HIGHEST_PERFORMANCE => Self::HighestPerformance,
+            reg_id::NOMINAL_PERFORMANCE => Self::NominalPerformance,
+            reg_id::LOWEST_NONLINEAR_PERFORMANCE => Self::LowestNonlinearPerformance,
+            reg_id::LOWEST_PERFORMANCE => Self::LowestPerformance,
+            reg_id::GUARANTEED_PERFORMANCE => Self::GuaranteedPerformance,
+            reg_id::DESIRED_PERFORMANCE => Self::DesiredPerformance,
+            reg_id::MINIMUM_PERFORMANCE => Self::MinimumPerformance,
+            reg_id::MAXIMUM_PERFORMANCE => Self::MaximumPerformance,
+            reg_id::PERFORMANCE_REDUCTION_TOLERANCE => Self::PerformanceReductionTolerance,
+            reg_id::TIME_WINDOW => Self::TimeWindow,
+            reg_id::COUNTER_WRAPAROUND_TIME => Self::CounterWraparoundTime,
+            reg_id::REFERENCE_PERFORMANCE_COUNTER => Self::ReferencePerformanceCounter,
+            reg_id::DELIVERED_PERFORMANCE_COUNTER => Self::DeliveredPerformanceCounter,
+            reg_id::PERFORMANCE_LIMITED => Self::PerformanceLimited,
+            reg_id::CPPC_ENABLE => Self::CppcEnable,
+            reg_id::AUTONOMOUS_SELECTION_ENABLE => Self::AutonomousSelectionEnable,
+            reg_id::AUTONOMOUS_ACTIVITY_WINDOW => Self::AutonomousActivityWindow,
+            reg_id::ENERGY_PERFORMANCE_PREFERENCE => Self::EnergyPerformancePreference,
+            reg_id::REFERENCE_PERFORMANCE => Self::ReferencePerformance,
+            reg_id::LOWEST_FREQUENCY => Self::LowestFrequency,
+            reg_id::NOMINAL_FREQUENCY => Self::NominalFrequency,
+            reg_id::TRANSITION_LATENCY => Self::TransitionLatency,
+            _ => Self::Reserved(raw),
+        }
+    }
+
+    /// Returns the raw register ID.
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        match self {
+            Self::HighestPerformance => reg_id::HIGHEST_PERFORMANCE,
+            Self::NominalPerformance => reg_id::NOMINAL_PERFORMANCE,
+            Self::LowestNonlinearPerformance => reg_id::LOWEST_NONLINEAR_PERFORMANCE,
+            Self::LowestPerformance => reg_id::LOWEST_PERFORMANCE,
+            Self::GuaranteedPerformance => reg_id::GUARANTEED_PERFORMANCE,
+            Self::DesiredPerformance => reg_id::DESIRED_PERFORMANCE,
+            Self::MinimumPerformance => reg_id::MINIMUM_PERFORMANCE,
+            Self::MaximumPerformance => reg_id::MAXIMUM_PERFORMANCE,
+            Self::PerformanceReductionTolerance => reg_id::PERFORMANCE_REDUCTION_TOLERANCE,
+            Self::TimeWindow => reg_id::TIME_WINDOW,
+            Self::CounterWraparoundTime => reg_id::COUNTER_WRAPAROUND_TIME,
+            Self::ReferencePerformanceCounter => reg_id::REFERENCE_PERFORMANCE_COUNTER,
+            Self::DeliveredPerformanceCounter => reg_id::DELIVERED_PERFORMANCE_COUNTER,
+            Self::PerformanceLimited => reg_id::PERFORMANCE_LIMITED,
+            Self::CppcEnable => reg_id::CPPC_ENABLE,
+            Self::AutonomousSelectionEnable => reg_id::AUTONOMOUS_SELECTION_ENABLE,
+            Self::AutonomousActivityWindow => reg_id::AUTONOMOUS_ACTIVITY_WINDOW,
+            Self::EnergyPerformancePreference => reg_id::ENERGY_PERFORMANCE_PREFERENCE,
+            Self::ReferencePerformance => reg_id::REFERENCE_PERFORMANCE,
+            Self::LowestFrequency => reg_id::LOWEST_FREQUENCY,
+            Self::NominalFrequency => reg_id::NOMINAL_FREQUENCY,
+            Self::TransitionLatency => reg_id::TRANSITION_LATENCY,
+            Self::Reserved(raw) => raw,
+        }
+    }
+
+    /// Returns `true` if this is a reserved register ID.
+    #[inline]
+    pub const fn is_reserved(self) -> bool {
+        matches!(self, Self::Reserved(_))
+    }
+
+    /// Returns the width of this register, or `None` if the register ID is reserved.
+    ///
+    /// Counter registers are either 32 or 64 bits wide as defined by the platform.
+    #[inline]
+    pub const fn width(self) -> Option<RegWidth> {
+        match self {
+            Self::Reserved(_) => None,
+            Self::CounterWraparoundTime
+            | Self::ReferencePerformanceCounter
+            | Self::DeliveredPerformanceCounter => Some(RegWidth::Platform { max: 64 }),
+            _ => Some(RegWidth::Fixed(32)),
+        }
+    }
+
+    /// Returns the access permission of this register, or `None` if the register ID is reserved.
+    #[inline]
+    pub const fn access(self) -> Option<RegAccess> {
+        match self {
+            Self::Reserved(_) => None,
+            Self::HighestPerformance
+            | Self::NominalPerformance
+            | Self::LowestNonlinearPerformance
+            | Self::LowestPerformance
+            | Self::GuaranteedPerformance
+            | Self::CounterWraparoundTime
+            | Self::ReferencePerformanceCounter
+            | Self::DeliveredPerformanceCounter
+            | Self::ReferencePerformance
+            | Self::LowestFrequency
+            | Self::NominalFrequency
+            | Self::TransitionLatency => Some(RegAccess::ReadOnly),
+            _ => Some(RegAccess::ReadWrite),
+        }
+    }
+
+    /// Checks if this register can be read by the CPPC read functions.
+    ///
+    /// Returns [`Error::InvalidParam`] for reserved register IDs, or [`Error::Denied`]
+    /// for write-only registers.
+    #[inline]
+    pub const fn check_read(self) -> Result<Self, Error> {
+        match self.access() {
+            None => Err(Error::InvalidParam),
+            Some(RegAccess::WriteOnly) => Err(Error::Denied),
+            Some(_) => Ok(self),
+        }
+    }
+
+    /// Checks if this register can be written by the CPPC write function.
+    ///
+    /// Returns [`Error::InvalidParam`] for reserved register IDs, or [`Error::Denied`]
+    /// for read-only registers.
+    #[inline]
+    pub const fn check_write(self) -> Result<Self, Error> {
+        match self.access() {
+            None => Err(Error::InvalidParam),
+            Some(RegAccess::ReadOnly) => Err(Error::Denied),
+            Some(_) => Ok(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CppcReg, RegAccess, RegWidth};
+    use crate::binary::Error;
+
+    #[test]
+    fn cppc_reg() {
+        for raw in (0..=0x14).chain([0x8000_0000]) {
+            let reg = CppcReg::from_raw(raw);
+            assert!(!reg.is_reserved());
+            assert_eq!(reg.raw(), raw);
+            assert!(reg.check_read().is_ok());
+        }
+        for raw in [0x15, 0x7fff_ffff, 0x8000_0001, 0xffff_ffff] {
+            let reg = CppcReg::from_raw(raw);
+            assert_eq!(reg, CppcReg::Reserved(raw));
+            assert_eq!((reg.width(), reg.access()), (None, None));
+            assert_eq!(reg.check_read(), Err(Error::InvalidParam));
+            assert_eq!(reg.check_write(), Err(Error::InvalidParam));
+        }
+        for reg in [
+            CppcReg::CounterWraparoundTime,
+            CppcReg::ReferencePerformanceCounter,
+            CppcReg::DeliveredPerformanceCounter,
+        ] {
+            assert_eq!(reg.width(), Some(RegWidth::Platform { max: 64 }));
+        }
+        let width = CppcReg::DeliveredPerformanceCounter.width().unwrap();
+        assert_eq!(
+            (width.max(), width.resolve(32), width.resolve(64)),
+            (64, Some(32), Some(64))
+        );
+        assert_eq!(width.resolve(16), None);
+        assert_eq!(
+            CppcReg::TransitionLatency.width(),
+            Some(RegWidth::Fixed(32))
+        );
+        assert_eq!(RegWidth::Fixed(32).resolve(64), Some(32));
+        assert_eq!(
+            CppcReg::TransitionLatency.access(),
+            Some(RegAccess::ReadOnly)
+        );
+        assert_eq!(
+            CppcReg::EnergyPerformancePreference.check_write(),
+            Ok(CppcReg::EnergyPerformancePreference)
+        );
+        assert_eq!(
+            CppcReg::ReferencePerformanceCounter.check_write(),
+            Err(Error::Denied)
+        );
+    }
+}
@@ -0,0 +1,692 @@
+//! Chapter 19. Debug Triggers Extension (EID #0x44425452 "DBTR").
+
+/// Extension ID for Debug Triggers Extension.
+pub const EID_DBTR: usize = crate::eid_from_str("DBTR") as _;
+pub use fid::*;
+
+/// Declared in §19.9.
+mod fid {
+    /// Function ID to get the number of debug triggers on the calling hart.
+    ///
+    /// Declared in §19.1.
+    pub const NUM_TRIGGERS: usize = 0;
+    /// Function ID to set and enable the shared memory for debug trigger configuration on the calling hart.
+    ///
+    /// Declared in §19.2.
+    pub const SET_SHMEM: usize = 1;
+    /// Function ID to read trigger state and configuration into shared memory.
+    ///
+    /// Declared in §19.3.
+    pub const READ_TRIGGERS: usize = 2;
+    /// Function ID to install debug triggers based on an array of trigger configurations.
+    ///
+    /// Declared in §19.4.
+    pub const INSTALL_TRIGGERS: usize = 3;
+    /// Function ID to update already installed debug triggers based on a trigger configuration array.
+    ///
+    /// Declared in §19.5.
+    pub const UPDATE_TRIGGERS: usize = 4;
+    /// Function ID to uninstall a set of debug triggers.
+    ///
+    /// Declared in §19.6.
+    pub const UNINSTALL_TRIGGERS: usize = 5;
+    /// Function ID to enable a set of debug triggers.
+    ///
+    /// Declared in §19.7.
+    pub const ENABLE_TRIGGERS: usize = 6;
+    /// Function ID to disable a set of debug triggers.
+    ///
+    /// Declared in §19.8.
+    pub const DISABLE_TRIGGERS: usize = 7;
+}
+
+/// Trigger types in the `type` field of `tdata1`.
+///
+/// Defined in RISC-V Debug Specification.
+pub mod trigger_type {
+    /// There is no trigger at this `tselect`.
+    pub const NONE: usize = 0;
+    /// Legacy SiFive address match trigger.
+    pub const LEGACY: usize = 1;
+    /// Address or data match trigger (`mcontrol`).
+    pub const MCONTROL: usize = 2;
+    /// Instruction count trigger (`icount`).
+    pub const ICOUNT: usize = 3;
+    /// Interrupt trigger (`itrigger`).
+    pub const ITRIGGER: usize = 4;
+    /// Exception trigger (`etrigger`).
+    pub const ETRIGGER: usize = 5;
+    /// Address or data match trigger, version 6 (`mcontrol6`).
+    pub const MCONTROL6: usize = 6;
+    /// External trigger (`tmexttrigger`).
+    pub const TMEXTTRIGGER: usize = 7;
+    /// Trigger exists but is currently disabled.
+    pub const DISABLED: usize = 15;
+}
+
+/// Debug trigger shared memory entry.
+///
+/// Each entry holds the state or index word followed by `tdata1`, `tdata2` and `tdata3`.
+/// The first word is the trigger state written by the read function, the trigger index
+/// written by the install function, or the trigger index read by the update function.
+/// An array of `trig_count` entries forms the shared memory passed to these functions.
+///
+/// Declared in §19.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct ShmemEntry {
+    word0: usize,
+    /// Value of trigger data register `tdata1`.
+    pub tdata1: Tdata1,
+    /// Value of trigger data register `tdata2`.
+    pub tdata2: usize,
+    /// Value of trigger data register `tdata3`.
+    pub tdata3: usize,
+}
+
+impl ShmemEntry {
+    /// Creates an entry with trigger data registers for the install function.
+    #[inline]
+    pub const fn install(tdata1: Tdata1, tdata2: usize, tdata3: usize) -> Self {
+        Self {
+            word0: 0,
+            tdata1,
+            tdata2,
+            tdata3,
+        }
+    }
+
+    /// Creates an entry with trigger index and trigger data registers for the update function.
+    #[inline]
+    pub const fn update(idx: usize, tdata1: Tdata1, tdata2: usize, tdata3: usize) -> Self {
+        Self {
+            word0: idx,
+            tdata1,
+            tdata2,
+            tdata3,
+        }
+    }
+
+    /// Creates an entry with trigger state and trigger data registers for the read function.
+    #[inline]
+    pub const fn read(tstate: TriggerState, tdata1: Tdata1, tdata2: usize, tdata3: usize) -> Self {
+        Self {
+            word0: tstate.raw(),
+            tdata1,
+            tdata2,
+            tdata3,
+        }
+    }
+
+    /// Returns the trigger state after the read function.
+    #[inline]
+    pub const fn tstate(&self) -> TriggerState {
+        TriggerState::from_raw(self.word0)
+    }
+
+    /// Returns the trigger index after the install function, or before the update function.
+    #[inline]
+    pub const fn idx(&self) -> usize {
+        self.word0
+    }
+
+    /// Sets the trigger index as the install function output.
+    #[inline]
+    pub fn set_idx(&mut self, idx: usize) {
+        self.word0 = idx;
+    }
+}
+
+/// Debug trigger state in shared memory.
+///
+/// Declared in §19.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct TriggerState {
+    raw: usize,
+}
+
+impl TriggerState {
+    const MAPPED: usize = 1 << 0;
+
+    /// Converts raw trigger state value into `TriggerState` structure.
+    #[inline]
+    pub const fn from_raw(raw: usize) -> Self {
+        Self { raw }
+    }
+
+    /// Creates a trigger state with the `MAPPED` bit.
+    #[inline]
+    pub const fn new(mapped: bool) -> Self {
+        Self {
+            raw: if mapped { Self::MAPPED } else { 0 },
+        }
+    }
+
+    /// Returns the raw trigger state value.
+    #[inline]
+    pub const fn raw(self) -> usize {
+        self.raw
+    }
+
+    /// Returns `true` if this trigger is mapped to a hardware trigger.
+    #[inline]
+    pub const fn is_mapped(self) -> bool {
+        self.raw & Self::MAPPED != 0
+    }
+}
+
+/// Value of trigger data register `tdata1`.
+///
+/// The highest 4 bits hold the trigger type, and the next bit is `dmode`;
+/// the remaining bits are defined by each trigger type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Tdata1 {
+    raw: usize,
+}
+
+impl Tdata1 {
+    const TYPE_SHIFT: u32 = usize::BITS - 4;
+    const DMODE: usize = 1 << (usize::BITS - 5);
+
+    /// Converts raw `tdata1` value into `Tdata1` structure.
+    #[inline]
+    pub const fn from_raw(raw: usize) -> Self {
+        Self { raw }
+    }
+
+    /// Returns the raw `tdata1` value.
+    #[inline]
+    pub const fn raw(self) -> usize {
+        self.raw
+    }
+
+    /// Returns the trigger type as defined in module `trigger_type`.
+    #[inline]
+    pub const fn trigger_type(self) -> usize {
+        self.raw >> Self::TYPE_SHIFT
+    }
+
+    /// Returns `true` if only debug mode can write this trigger.
+    #[inline]
+    pub const fn dmode(self) -> bool {
+        self.raw & Self::DMODE != 0
+    }
+
+    /// Decodes this `tdata1` value by its trigger type.
+    #[inline]
+    pub const fn decode(self) -> Trigger {
+        match self.trigger_type() {
+            trigger_type::NONE => Trigger::None,
+            trigger_type::MCONTROL6 => Trigger::Mcontrol6(Mcontrol6 { raw: self.raw }),
+            trigger_type::ICOUNT => Trigger::Icount(Icount { raw: self.raw }),
+            trigger_type::DISABLED => Trigger::Disabled,
+            _ => Trigger::Other(self),
+        }
+    }
+}
+
+/// Trigger configuration decoded from `tdata1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trigger {
+    /// There is no trigger.
+    None,
+    /// Address or data match trigger, version 6.
+    Mcontrol6(Mcontrol6),
+    /// Instruction count trigger.
+    Icount(Icount),
+    /// Trigger exists but is currently disabled.
+    Disabled,
+    /// Trigger of other types, including `mcontrol`, `itrigger`, `etrigger` and `tmexttrigger`.
+    Other(Tdata1),
+}
+
+/// Address or data match trigger `tdata1` layout (`mcontrol6`).
+///
+/// # Examples
+///
+/// Configure a supervisor-mode watchpoint on stores.
+///
+/// ```
+/// # use sbi_spec::dbtr::{Mcontrol6, Trigger, trigger_type};
+/// let tdata1 = Mcontrol6::new().with_store(true).with_s(true).into_tdata1();
+/// assert_eq!(tdata1.trigger_type(), trigger_type::MCONTROL6);
+/// let Trigger::Mcontrol6(config) = tdata1.decode() else { unreachable!() };
+/// assert!(config.store() && config.s() && !config.load());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Mcontrol6 {
+    raw: usize,
+}
+
+impl Mcontrol6 {
+    /// Creates a `mcontrol6` trigger with all fields cleared.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            raw: trigger_type::MCONTROL6 << Tdata1::TYPE_SHIFT,
+        }
+    }
+
+    /// Converts into `tdata1` value.
+    #[inline]
+    pub const fn into_tdata1(self) -> Tdata1 {
+        Tdata1 { raw: self.raw }
+    }
+
+    /// Returns `true` if this trigger matches load addresses or data.
+    #[inline]
+    pub const fn load(self) -> bool {
+        get_bit(self.raw, 0)
+    }
+
+    /// Returns `true` if this trigger matches store addresses or data.
+    #[inline]
+    pub const fn store(self) -> bool {
+        get_bit(self.raw, 1)
+    }
+
+    /// Returns `true` if this trigger matches instruction execution addresses or opcodes.
+    #[inline]
+    pub const fn execute(self) -> bool {
+        get_bit(self.raw, 2)
+    }
+
+    /// Returns `true` if this trigger is enabled in user-mode.
+    #[inline]
+    pub const fn u(self) -> bool {
+        get_bit(self.raw, 3)
+    }
+
+    /// Returns `true` if this trigger is enabled in supervisor-mode.
+    #[inline]
+    pub const fn s(self) -> bool {
+        get_bit(self.raw, 4)
+    }
+
+    /// Returns `true` if this trigger may fire on uncertain matches.
+    #[inline]
+    pub const fn uncertainen(self) -> bool {
+        get_bit(self.raw, 5)
+    }
+
+    /// Returns `true` if this trigger is enabled in machine-mode.
+    #[inline]
+    pub const fn m(self) -> bool {
+        get_bit(self.raw, 6)
+    }
+
+    /// Returns the match type of this trigger.
+    #[inline]
+    pub const fn match_type(self) -> usize {
+        get_field(self.raw, 7, 4)
+    }
+
+    /// Returns `true` if this trigger is chained with the next trigger.
+    #[inline]
+    pub const fn chain(self) -> bool {
+        get_bit(self.raw, 11)
+    }
+
+    /// Returns the action to take when this trigger fires.
+    #[inline]
+    pub const fn action(self) -> usize {
+        get_field(self.raw, 12, 4)
+    }
+
+    /// Returns the access size this trigger matches.
+    #[inline]
+    pub const fn size(self) -> usize {
+        get_field(self.raw, 16, 3)
+    }
+
+    /// Returns `true` if this trigger matches on data value instead of address.
+    #[inline]
+    pub const fn select(self) -> bool {
+        get_bit(self.raw, 21)
+    }
+
+    /// Returns the `hit1` and `hit0` bits as a two-bit value.
+    #[inline]
+    pub const fn hit(self) -> usize {
+        ((get_bit(self.raw, 25) as usize) << 1) | get_bit(self.raw, 22) as usize
+    }
+
+    /// Returns `true` if this trigger is enabled in virtual user-mode.
+    #[inline]
+    pub const fn vu(self) -> bool {
+        get_bit(self.raw, 23)
+    }
+
+    /// Returns `true` if this trigger is enabled in virtual supervisor-mode.
+    #[inline]
+    pub const fn vs(self) -> bool {
+        get_bit(self.raw, 24)
+    }
+
+    /// Returns `true` if this trigger fired on an uncertain match.
+    #[inline]
+    pub const fn uncertain(self) -> bool {
+        get_bit(self.raw, 26)
+    }
+
+    /// Sets whether this trigger matches load addresses or data.
+    #[inline]
+    pub const fn with_load(self, value: bool) -> Self {
+        Self {
+            raw: set_bit(self.raw, 0, value),
+        }
+    }
+
+    /// Sets whether this trigger matches store addresses or data.
+    #[inline]
+    pub const fn with_store(self, value: bool) -> Self {
+        Self {
+            raw: set_bit(self.raw, 1, value),
+        }
+    }
+
+    /// Sets whether this trigger matches instruction execution addresses or opcodes.
+    #[inline]
+    pub const fn with_execute(self, value: bool) -> Self {
+        Self {
+            raw: set_bit(self.raw, 2, value),
+        }
+    }
+
+    /// Sets whether this trigger is enabled in user-mode.
+    #[inline]
+    pub const fn with_u(self, value: bool) -> Self {
+        Self {
+            raw: set_bit(self.raw, 3, value),
+        }
+    }
+
+    /// Sets whether this trigger is enabled in supervisor-mode.
+    #[inline]
+    pub const fn with_s(self, value: bool) -> Self {
+        Self {
+            raw: set_bit(self.raw, 4, value),
+        }
+    }
+
+    /// Sets the match type of this trigger.
+    #[inline]
+    pub const fn with_match_type(self, value: usize) -> Self {
+        Self {
+            raw: set_field(self.raw, 7, 4, value),
+        }
+    }
+
+    /// Sets whether this trigger is chained with the next trigger.
+    #[inline]
+    pub const fn with_chain(self, value: bool) -> Self {
+        Self {
+            raw: set_bit(self.raw, 11, value),
+        }
+    }
+
+    /// Sets the action to take when this trigger fires.
+    #[inline]
+    pub const fn with_action(self, value: usize) -> Self {
+        Self {
+            raw: set_field(self.raw, 12, 4, value),
+        }
+    }
+
+    /// Sets the access size this trigger matches.
+    #[inline]
+    pub const fn with_size(self, value: usize) -> Self {
+        Self {
+            raw: set_field(self.raw, 16, 3, value),
+        }
+    }
+
+    /// Sets whether this trigger matches on data value instead of address.
+    #[inline]
+    pub const fn with_select(self, value: bool) -> Self {
+        Self {
+            raw: set_bit(self.raw, 21, value),
+        }
+    }
+
+    /// Sets whether this trigger is enabled in virtual user-mode.
+    #[inline]
+    pub const fn with_vu(self, value: bool) -> Self {
+        Self {
+            raw: set_bit(self.raw, 23, value),
+        }
+    }
+
+    /// Sets whether this trigger is enabled in virtual supervisor-mode.
+    #[inline]
+    pub const fn with_vs(self, value: bool) -> Self {
+        Self {
+            raw: set_bit(self.raw, 24, value),
+        }
+    }
+}
+
+impl Default for Mcontrol6 {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Instruction count trigger `tdata1` layout (`icount`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Icount {
+    raw: usize,
+}
+
+impl Icount {
+    /// Creates an `icount` trigger with all fields cleared.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            raw: trigger_type::ICOUNT << Tdata1::TYPE_SHIFT,
+        }
+    }
+
+    /// Converts into `tdata1` value.
+    #[inline]
+    pub const fn into_tdata1(self) -> Tdata1 {
+        Tdata1 { raw: self.raw }
+    }
+
+    /// Returns the action to take when this trigger fires.
+    #[inline]
+    pub const fn action(self) -> usize {
+        get_field(self.raw, 0, 6)
+    }
+
+    /// Returns `true` if this trigger is enabled in user-mode.
+    #[inline]
+    pub const fn u(self) -> bool {
+        get_bit(self.raw, 6)
+    }
+
+    /// Returns `true` if this trigger is enabled in supervisor-mode.
+    #[inline]
+    pub const fn s(self) -> bool {
+        get_bit(self.raw, 7)
+    }
+
+    /// Returns `true` if this trigger is pending to fire.
+    #[inline]
+    pub const fn pending(self) -> bool {
+        get_bit(self.raw, 8)
+    }
+
+    /// Returns `true` if this trigger is enabled in machine-mode.
+    #[inline]
+    pub const fn m(self) -> bool {
+        get_bit(self.raw, 9)
+    }
+
+    /// Returns the remaining instruction count before this trigger fires.
+    #[inline]
+    pub const fn count(self) -> usize {
+        get_field(self.raw, 10, 14)
+    }
+
+    /// Returns `true` if this trigger has fired.
+    #[inline]
+    pub const fn hit(self) -> bool {
+        get_bit(self.raw, 24)
+    }
+
+    /// Returns `true` if this trigger is enabled in virtual user-mode.
+    #[inline]
+    pub const fn vu(self) -> bool {
+        get_bit(self.raw, 25)
+    }
+
+    /// Returns `true` if this trigger is enabled in virtual supervisor-mode.
+    #[inline]
+    pub const fn vs(self) -> bool {
+        get_bit(self.raw, 26)
+    }
+
+    /// Sets the action to take when this trigger fires.
+    #[inline]
+    pub const fn with_action(self, value: usize) -> Self {
+        Self {
+            raw: set_field(self.raw, 0, 6, value),
+        }
+    }
+
+    /// Sets whether this trigger is enabled in user-mode.
+    #[inline]
+    pub const fn with_u(self, value: bool) -> Self {
+        Self {
+            raw: set_bit(self.raw, 6, value),
+        }
+    }
+
+    /// Sets whether this trigger is enabled in supervisor-mode.
+    #[inline]
+    pub const fn with_s(self, value: bool) -> Self {
+        Self {
+            raw: set_bit(self.raw, 7, value),
+        }
+    }
+
+    /// Sets the instruction count before this trigger fires.
+    #[inline]
+    pub const fn with_count(self, value: usize) -> Self {
+        Self {
+            raw: set_field(self.raw, 10, 14, value),
+        }
+    }
+
+    /// Sets whether this trigger is enabled in virtual user-mode.
+    #[inline]
+    pub const fn with_vu(self, value: bool) -> Self {
+        Self {
+            raw: set_bit(self.raw, 25, value),
+        }
+    }
+
+    /// Sets whether this trigger is enabled in virtual supervisor-mode.
+    #[inline]
+    pub const fn with_vs(self, value: bool) -> Self {
+        Self {
+            raw: set_bit(self.raw, 26, value),
+        }
+    }
+}
+
+impl Default for Icount {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[inline]
+const fn get_bit(raw: usize, bit: u32) -> bool {
+    raw & (1 << bit) != 0
+}
+
+#[inline]
+const fn set_bit(raw: usize, bit: u32, value: bool) -> usize {
+    if value {
+        raw | (1 << bit)
+    } else {
+        raw & !(1 << bit)
+    }
+}
+
+#[inline]
+const fn get_field(raw: usize, shift: u32, width: u32) -> usize {
+    (raw >> shift) & ((1 << width) - 1)
+}
+
+#[inline]
+const fn set_field(raw: usize, shift: u32, width: u32, value: usize) -> usize {
+    let mask = ((1 << width) - 1) << shift;
+    (raw & !mask) | ((value << shift) & mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{trigger_type, Icount, Mcontrol6, ShmemEntry, Tdata1, Trigger, TriggerState};
+
+    #[test]
+    fn tdata1_decode() {
+        let tdata1 = Mcontrol6::new()
+            .with_load(true)
+            .with_execute(true)
+            .with_u(true)
+            .with_match_type(1)
+            .with_action(1)
+            .with_size(3)
+            .with_vs(true)
+            .into_tdata1();
+        let Trigger::Mcontrol6(config) = tdata1.decode() else {
+            panic!("expected mcontrol6, got {:?}", tdata1.decode());
+        };
+        assert!(config.load() && config.execute() && config.u() && config.vs());
+        assert!(!config.store() && !config.s() && !config.m() && !config.vu());
+        assert_eq!(config.match_type(), 1);
+        assert_eq!(config.action(), 1);
+        assert_eq!(config.size(), 3);
+        assert_eq!(config.hit(), 0);
+        assert!(!tdata1.dmode());
+        // bits beyond field width do not leak into neighbouring fields
+        let config = Mcontrol6::new().with_match_type(0x1f);
+        assert_eq!(config.match_type(), 0xf);
+        assert!(!config.chain());
+
+        let tdata1 = Icount::new().with_count(1).with_s(true).into_tdata1();
+        assert_eq!(tdata1.trigger_type(), trigger_type::ICOUNT);
+        let Trigger::Icount(config) = tdata1.decode() else {
+            panic!("expected icount, got {:?}", tdata1.decode());
+        };
+        assert_eq!(config.count(), 1);
+        assert!(config.s() && !config.u() && !config.pending());
+
+        let disabled = Tdata1::from_raw(trigger_type::DISABLED << (usize::BITS - 4));
+        assert_eq!(disabled.decode(), Trigger::Disabled);
+        assert_eq!(Tdata1::from_raw(0).decode(), Trigger::None);
+        let mcontrol = Tdata1::from_raw(trigger_type::MCONTROL << (usize::BITS - 4));
+        assert_eq!(mcontrol.decode(), Trigger::Other(mcontrol));
+    }
+
+    #[test]
+    fn shmem_entry() {
+        let tdata1 = Mcontrol6::new().with_store(true).into_tdata1();
+        let mut entry = ShmemEntry::install(tdata1, 0x8000_1000, 0);
+        entry.set_idx(3);
+        assert_eq!(entry.idx(), 3);
+        assert_eq!(entry, ShmemEntry::update(3, tdata1, 0x8000_1000, 0));
+        let entry = ShmemEntry::read(TriggerState::new(true), tdata1, 0x8000_1000, 0);
+        assert!(entry.tstate().is_mapped());
+        assert!(!TriggerState::new(false).is_mapped());
+    }
+}
@@ -0,0 +1,262 @@
+//! Chapter 18. Firmware Features Extension (EID #0x46574654 "FWFT").
+
+use crate::binary::Error;
+
+/// Extension ID for Firmware Features Extension.
+pub const EID_FWFT: usize = crate::eid_from_str("FWFT") as _;
+pub use fid::*;
+
+/// Declared in §18.3.
+mod fid {
+    /// Function ID to set the configuration value of a specific firmware feature.
+    ///
+    /// Declared in §18.1.
+    pub const SET: usize = 0;
+    /// Function ID to get the configuration value of a specific firmware feature.
+    ///
+    /// Declared in §18.2.
+    pub const GET: usize = 1;
+}
+
+/// Firmware feature IDs.
+///
+/// Bit 31 of the feature ID selects a global feature, while bit 30 selects a
+/// platform-specific feature. Standard feature IDs not listed here are reserved.
+///
+/// Declared in §18.
+pub mod feature_type {
+    /// Control misaligned access exception delegation to supervisor-mode.
+    pub const MISALIGNED_EXC_DELEG: u32 = 0;
+    /// Control landing pad support for supervisor-mode.
+    pub const LANDING_PAD: u32 = 1;
+    /// Control shadow stack support for supervisor-mode.
+    pub const SHADOW_STACK: u32 = 2;
+    /// Control double trap support.
+    pub const DOUBLE_TRAP: u32 = 3;
+    /// Control hardware updating of PTE A/D bits.
+    pub const PTE_AD_HW_UPDATING: u32 = 4;
+    /// Control the pointer masking tag length.
+    pub const POINTER_MASKING_PMLEN: u32 = 5;
+
+    /// Feature ID bit for global features; local features are configured per hart.
+    pub const GLOBAL: u32 = 1 << 31;
+    /// Feature ID bit for platform-specific features.
+    pub const PLATFORM: u32 = 1 << 30;
+}
+
+/// Flags for the firmware feature set function.
+///
+/// Declared in §18.1.
+pub mod flags {
+    /// Lock the feature value; further set requests would fail until the next reset.
+    pub const LOCK: usize = 1 << 0;
+}
+
+/// Standard firmware feature.
+///
+/// Declared in §18.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// Misaligned access exception delegation, `0` to disable or `1` to enable.
+    MisalignedExcDeleg,
+    /// Landing pad, `0` to disable or `1` to enable.
+    LandingPad,
+    /// Shadow stack, `0` to disable or `1` to enable.
+    ShadowStack,
+    /// Double trap, `0` to disable or `1` to enable.
+    DoubleTrap,
+    /// Hardware updating of PTE A/D bits, `0` to disable or `1` to enable.
+    PteAdHwUpdating,
+    /// Pointer masking tag length `PMLEN`, `0` to disable.
+    ///
+    /// SBI implementations would choose the smallest supported `PMLEN` that is
+    /// not less than the requested value.
+    PointerMaskingPmlen,
+}
+
+impl Feature {
+    /// Converts a raw feature ID into a standard feature, if any.
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Option<Self> {
+        match raw {
+            feature_type::MISALIGNED_EXC_DELEG => Some(Self::MisalignedExcDeleg),
+            feature_type::LANDING_PAD => Some(Self::LandingPad),
+            feature_type::SHADOW_STACK => Some(Self::ShadowStack),
+            feature_type::DOUBLE_TRAP => Some(Self::DoubleTrap),
+            feature_type::PTE_AD_HW_UPDATING => Some(Self::PteAdHwUpdating),
+            feature_type::POINTER_MASKING_PMLEN => Some(Self::PointerMaskingPmlen),
+            _ => None,
+        }
+    }
+
+    /// Returns the raw feature ID of this feature.
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        match self {
+            Self::MisalignedExcDeleg => feature_type::MISALIGNED_EXC_DELEG,
+            Self::LandingPad => feature_type::LANDING_PAD,
+            Self::ShadowStack => feature_type::SHADOW_STACK,
+            Self::DoubleTrap => feature_type::DOUBLE_TRAP,
+            Self::PteAdHwUpdating => feature_type::PTE_AD_HW_UPDATING,
+            Self::PointerMaskingPmlen => feature_type::POINTER_MASKING_PMLEN,
+        }
+    }
+
+    /// Returns the largest value allowed for this feature.
+    ///
+    /// Every feature accepts values from `0` to this value inclusive.
+    #[inline]
+    pub const fn max_value(self) -> usize {
+        match self {
+            Self::PointerMaskingPmlen => usize::BITS as usize - 1,
+            _ => 1,
+        }
+    }
+
+    /// Checks if `value` is allowed for this feature.
+    ///
+    /// Returns [`Error::InvalidParam`] if `value` is out of range.
+    #[inline]
+    pub const fn check_value(self, value: usize) -> Result<(), Error> {
+        if value > self.max_value() {
+            return Err(Error::InvalidParam);
+        }
+        Ok(())
+    }
+}
+
+/// Firmware feature ID classified by the feature ID space.
+///
+/// Declared in §18.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FeatureId {
+    /// Standard feature defined by the SBI specification.
+    Standard(Feature),
+    /// Reserved standard feature ID for future use.
+    Reserved(u32),
+    /// Platform-specific feature ID.
+    Platform(u32),
+}
+
+impl FeatureId {
+    /// Classifies a raw feature ID.
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        if raw & feature_type::PLATFORM != 0 {
+            return Self::Platform(raw);
+        }
+        match Feature::from_raw(raw) {
+            Some(feature) => Self::Standard(feature),
+            None => Self::Reserved(raw),
+        }
+    }
+
+    /// Returns the raw feature ID.
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        match self {
+            Self::Standard(feature) => feature.raw(),
+            Self::Reserved(raw) | Self::Platform(raw) => raw,
+        }
+    }
+
+    /// Returns `true` if this feature is global, or `false` if it is configured per hart.
+    #[inline]
+    pub const fn is_global(self) -> bool {
+        self.raw() & feature_type::GLOBAL != 0
+    }
+}
+
+/// Checks the `flags` parameter of the firmware feature set function.
+///
+/// Returns [`Error::InvalidParam`] if any reserved bit is set.
+#[inline]
+pub const fn check_flags(flags: usize) -> Result<(), Error> {
+    if flags & !flags::LOCK != 0 {
+        return Err(Error::InvalidParam);
+    }
+    Ok(())
+}
+
+/// Checks parameters of the firmware feature set function on a standard feature.
+///
+/// Returns [`Error::Denied`] if `feature` is reserved, or [`Error::InvalidParam`]
+/// if `value` or `flags` is invalid. Platform-specific features are not checked
+/// and also return [`Error::Denied`]; SBI implementations supporting them should
+/// check their values before calling this function.
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::{binary::Error, fwft::{self, Feature}};
+/// let ret = fwft::check_set(fwft::feature_type::SHADOW_STACK, 1, fwft::flags::LOCK);
+/// assert_eq!(ret, Ok(Feature::ShadowStack));
+/// let ret = fwft::check_set(fwft::feature_type::DOUBLE_TRAP, 2, 0);
+/// assert_eq!(ret, Err(Error::InvalidParam));
+/// let ret = fwft::check_set(0x100, 1, 0);
+/// assert_eq!(ret, Err(Error::Denied));
+/// ```
+#[inline]
+pub const fn check_set(feature: u32, value: usize, flags: usize) -> Result<Feature, Error> {
+    let FeatureId::Standard(feature) = FeatureId::from_raw(feature) else {
+        return Err(Error::Denied);
+    };
+    if let Err(e) = feature.check_value(value) {
+        return Err(e);
+    }
+    if let Err(e) = check_flags(flags) {
+        return Err(e);
+    }
+    Ok(feature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_flags, feature_type, flags, Feature, FeatureId};
+    use crate::binary::Error;
+
+    #[test]
+    fn feature_id_space() {
+        assert_eq!(
+            FeatureId::from_raw(feature_type::PTE_AD_HW_UPDATING),
+            FeatureId::Standard(Feature::PteAdHwUpdating)
+        );
+        assert_eq!(FeatureId::from_raw(6), FeatureId::Reserved(6));
+        assert_eq!(
+            FeatureId::from_raw(0x3fff_ffff),
+            FeatureId::Reserved(0x3fff_ffff)
+        );
+        assert_eq!(
+            FeatureId::from_raw(0x4000_0000),
+            FeatureId::Platform(0x4000_0000)
+        );
+        assert_eq!(
+            FeatureId::from_raw(0x8000_0000),
+            FeatureId::Reserved(0x8000_0000)
+        );
+        assert_eq!(
+            FeatureId::from_raw(0xc000_0001),
+            FeatureId::Platform(0xc000_0001)
+        );
+        assert!(!FeatureId::from_raw(0x4000_0000).is_global());
+        assert!(FeatureId::from_raw(0xc000_0000).is_global());
+        assert!(!FeatureId::Standard(Feature::DoubleTrap).is_global());
+    }
+
+    #[test]
+    fn feature_values() {
+        for raw in 0..=5 {
+            let feature = Feature::from_raw(raw).unwrap();
+            assert_eq!(feature.raw(), raw);
+            assert_eq!(feature.check_value(0), Ok(()));
+            assert_eq!(feature.check_value(1), Ok(()));
+            assert_eq!(feature.check_value(usize::MAX), Err(Error::InvalidParam));
+        }
+        assert_eq!(Feature::LandingPad.check_value(2), Err(Error::InvalidParam));
+        assert_eq!(Feature::PointerMaskingPmlen.check_value(7), Ok(()));
+        assert_eq!(Feature::PointerMaskingPmlen.check_value(16), Ok(()));
+        assert_eq!(check_flags(0), Ok(()));
+        assert_eq!(check_flags(flags::LOCK), Ok(()));
+        assert_eq!(check_flags(0b10), Err(Error::InvalidParam));
+    }
+}
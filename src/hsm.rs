@@ -4,6 +4,10 @@
 pub const EID_HSM: usize = crate::eid_from_str("HSM") as _;
 pub use fid::*;
 
+use crate::binary::Error;
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{AtomicUsize, Ordering};
+
 /// Hart states.
 ///
 /// Declared in Table 1 at §9.
@@ -49,6 +53,138 @@ pub mod suspend_type {
     pub const NON_RETENTIVE: u32 = 0x8000_0000;
 }
 
+/// Hart suspend type classified by the suspend type space.
+///
+/// | Value                         | Description                          |
+/// |:------------------------------|:-------------------------------------|
+/// | `0x0000_0000`                 | Default retentive suspend            |
+/// | `0x0000_0001..=0x0FFF_FFFF`   | Reserved for future use              |
+/// | `0x1000_0000..=0x7FFF_FFFF`   | Platform specific retentive suspend  |
+/// | `0x8000_0000`                 | Default non-retentive suspend        |
+/// | `0x8000_0001..=0x8FFF_FFFF`   | Reserved for future use              |
+/// | `0x9000_0000..=0xFFFF_FFFF`   | Platform specific non-retentive suspend |
+///
+/// Declared in §9.4.
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::{binary::Error, hsm::{SuspendSupport, SuspendType}};
+/// let suspend_type = SuspendType::from_raw(0x9000_0001);
+/// assert_eq!(suspend_type, SuspendType::PlatformNonRetentive(0x9000_0001));
+/// assert!(suspend_type.has_resume_addr());
+/// let support = |ty| match ty {
+///     SuspendType::DefaultRetentive => SuspendSupport::Supported,
+///     _ => SuspendSupport::Unimplemented,
+/// };
+/// assert_eq!(suspend_type.check(support), Err(Error::InvalidParam));
+/// assert_eq!(SuspendType::from_raw(0x1).check(support), Err(Error::InvalidParam));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SuspendType {
+    /// Default retentive suspend.
+    DefaultRetentive,
+    /// Default non-retentive suspend.
+    DefaultNonRetentive,
+    /// Platform specific retentive suspend.
+    PlatformRetentive(u32),
+    /// Platform specific non-retentive suspend.
+    PlatformNonRetentive(u32),
+    /// Reserved suspend type for future use.
+    Reserved(u32),
+}
+
+impl SuspendType {
+    const NON_RETENTIVE_BIT: u32 = 1 << 31;
+    const PLATFORM_MASK: u32 = 0x7000_0000;
+
+    /// Classifies a raw suspend type.
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        match raw {
+            suspend_type::RETENTIVE => Self::DefaultRetentive,
+            suspend_type::NON_RETENTIVE => Self::DefaultNonRetentive,
+            _ if raw & Self::PLATFORM_MASK == 0 => Self::Reserved(raw),
+            _ if raw & Self::NON_RETENTIVE_BIT == 0 => Self::PlatformRetentive(raw),
+            _ => Self::PlatformNonRetentive(raw),
+        }
+    }
+
+    /// Returns the raw suspend type.
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        match self {
+            Self::DefaultRetentive => suspend_type::RETENTIVE,
+            Self::DefaultNonRetentive => suspend_type::NON_RETENTIVE,
+            Self::PlatformRetentive(raw)
+            | Self::PlatformNonRetentive(raw)
+            | Self::Reserved(raw) => raw,
+        }
+    }
+
+    /// Returns `true` if this is a retentive suspend type.
+    ///
+    /// Bit 31 of a suspend type is clear for retentive suspend, including reserved values.
+    #[inline]
+    pub const fn is_retentive(self) -> bool {
+        self.raw() & Self::NON_RETENTIVE_BIT == 0
+    }
+
+    /// Returns `true` if this is a platform specific suspend type.
+    #[inline]
+    pub const fn is_platform(self) -> bool {
+        matches!(
+            self,
+            Self::PlatformRetentive(_) | Self::PlatformNonRetentive(_)
+        )
+    }
+
+    /// Returns `true` if this is a reserved suspend type.
+    #[inline]
+    pub const fn is_reserved(self) -> bool {
+        matches!(self, Self::Reserved(_))
+    }
+
+    /// Returns `true` if `resume_addr` and `opaque` parameters of the hart suspend function
+    /// are meaningful.
+    ///
+    /// They are only used by non-retentive suspend, where the hart resumes at `resume_addr`
+    /// in supervisor-mode with `opaque` in register `a1`. A retentive suspend returns to
+    /// the caller of the hart suspend function and ignores both parameters.
+    #[inline]
+    pub const fn has_resume_addr(self) -> bool {
+        !self.is_reserved() && !self.is_retentive()
+    }
+
+    /// Checks this suspend type with its support on the current platform.
+    ///
+    /// `support` is only called for suspend types that are not reserved. Returns
+    /// [`Error::InvalidParam`] if the suspend type is reserved or is platform specific
+    /// and unimplemented, and [`Error::NotSupported`] if the suspend type is implemented
+    /// but misses dependencies, or if a default suspend type is not implemented.
+    pub fn check(self, support: impl FnOnce(Self) -> SuspendSupport) -> Result<Self, Error> {
+        if self.is_reserved() {
+            return Err(Error::InvalidParam);
+        }
+        match support(self) {
+            SuspendSupport::Supported => Ok(self),
+            SuspendSupport::Unimplemented if self.is_platform() => Err(Error::InvalidParam),
+            _ => Err(Error::NotSupported),
+        }
+    }
+}
+
+/// Support of a suspend type on the current platform.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SuspendSupport {
+    /// The suspend type is implemented and can be entered.
+    Supported,
+    /// The suspend type is implemented, but the platform misses one or more dependencies.
+    MissingDependencies,
+    /// The suspend type is not implemented.
+    Unimplemented,
+}
+
 /// Declared in §9.5.
 mod fid {
     /// Function ID to start executing the given hart at specified address in supervisor-mode.
@@ -68,3 +204,406 @@ mod fid {
     /// Declared in §9.4.
     pub const HART_SUSPEND: usize = 3;
 }
+
+/// Hart state in the HSM state machine.
+///
+/// Requests of HSM functions are legal from the following states, and illegal requests
+/// must produce the listed errors:
+///
+/// | Request        | Legal transition              | Error from other states                                      |
+/// |:---------------|:------------------------------|:-------------------------------------------------------------|
+/// | `HART_START`   | `STOPPED` → `START_PENDING`   | `already_available` from `STARTED`, otherwise `invalid_param` |
+/// | `HART_STOP`    | `STARTED` → `STOP_PENDING`    | `failed`                                                     |
+/// | `HART_SUSPEND` | `STARTED` → `SUSPEND_PENDING` | `failed`                                                     |
+///
+/// The SBI implementation then completes pending states by `START_PENDING` → `STARTED`,
+/// `STOP_PENDING` → `STOPPED`, `SUSPEND_PENDING` → `SUSPENDED`, and resumes a suspended
+/// hart by `SUSPENDED` → `RESUME_PENDING` → `STARTED`.
+///
+/// Declared in §9.
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::{binary::{Error, SbiRet}, hsm::HartState};
+/// let state = HartState::Stopped.start().unwrap();
+/// assert_eq!(state.start(), Err(Error::InvalidParam));
+/// let state = state.started().unwrap();
+/// assert_eq!(SbiRet::from(state.start().unwrap_err()), SbiRet::already_available());
+/// assert_eq!(state.suspend(), Ok(HartState::SuspendPending));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(usize)]
+pub enum HartState {
+    /// The hart is physically powered-up and executing normally.
+    Started = hart_state::STARTED,
+    /// The hart is not executing in supervisor-mode or any lower privilege mode.
+    Stopped = hart_state::STOPPED,
+    /// Some other hart has requested to start the hart from the `STOPPED` state.
+    StartPending = hart_state::START_PENDING,
+    /// The hart has requested to stop itself from the `STARTED` state.
+    StopPending = hart_state::STOP_PENDING,
+    /// The hart is in a platform specific suspend (or low power) state.
+    Suspended = hart_state::SUSPENDED,
+    /// The hart has requested to suspend itself from the `STARTED` state.
+    SuspendPending = hart_state::SUSPEND_PENDING,
+    /// An interrupt or platform specific hardware event has caused the hart to resume
+    /// from the `SUSPENDED` state.
+    ResumePending = hart_state::RESUME_PENDING,
+}
+
+impl HartState {
+    /// Converts a raw hart state into `HartState`, if valid.
+    #[inline]
+    pub const fn from_raw(raw: usize) -> Option<Self> {
+        match raw {
+            hart_state::STARTED => Some(Self::Started),
+            hart_state::STOPPED => Some(Self::Stopped),
+            hart_state::START_PENDING => Some(Self::StartPending),
+            hart_state::STOP_PENDING => Some(Self::StopPending),
+            hart_state::SUSPENDED => Some(Self::Suspended),
+            hart_state::SUSPEND_PENDING => Some(Self::SuspendPending),
+            hart_state::RESUME_PENDING => Some(Self::ResumePending),
+            _ => None,
+        }
+    }
+
+    /// Returns the raw hart state, as returned by the hart get status function.
+    #[inline]
+    pub const fn raw(self) -> usize {
+        self as usize
+    }
+
+    /// Returns `true` if the hart is in one of the pending states.
+    #[inline]
+    pub const fn is_pending(self) -> bool {
+        matches!(
+            self,
+            Self::StartPending | Self::StopPending | Self::SuspendPending | Self::ResumePending
+        )
+    }
+
+    /// State after the hart start function on the target hart.
+    ///
+    /// Returns [`Error::AlreadyAvailable`] if the hart is already started, or
+    /// [`Error::InvalidParam`] if the hart is in other states than `STOPPED`.
+    #[inline]
+    pub const fn start(self) -> Result<Self, Error> {
+        match self {
+            Self::Stopped => Ok(Self::StartPending),
+            Self::Started => Err(Error::AlreadyAvailable),
+            _ => Err(Error::InvalidParam),
+        }
+    }
+
+    /// State after the hart stop function on the calling hart.
+    ///
+    /// Returns [`Error::Failed`] if the hart is not started.
+    #[inline]
+    pub const fn stop(self) -> Result<Self, Error> {
+        match self {
+            Self::Started => Ok(Self::StopPending),
+            _ => Err(Error::Failed),
+        }
+    }
+
+    /// State after the hart suspend function on the calling hart.
+    ///
+    /// Returns [`Error::Failed`] if the hart is not started.
+    #[inline]
+    pub const fn suspend(self) -> Result<Self, Error> {
+        match self {
+            Self::Started => Ok(Self::SuspendPending),
+            _ => Err(Error::Failed),
+        }
+    }
+
+    /// State after the SBI implementation has started the hart.
+    ///
+    /// Returns [`Error::Failed`] if the hart is not pending to start.
+    #[inline]
+    pub const fn started(self) -> Result<Self, Error> {
+        match self {
+            Self::StartPending | Self::ResumePending => Ok(Self::Started),
+            _ => Err(Error::Failed),
+        }
+    }
+
+    /// State after the SBI implementation has stopped the hart.
+    ///
+    /// Returns [`Error::Failed`] if the hart is not pending to stop.
+    #[inline]
+    pub const fn stopped(self) -> Result<Self, Error> {
+        match self {
+            Self::StopPending => Ok(Self::Stopped),
+            _ => Err(Error::Failed),
+        }
+    }
+
+    /// State after the SBI implementation has suspended the hart.
+    ///
+    /// Returns [`Error::Failed`] if the hart is not pending to suspend.
+    #[inline]
+    pub const fn suspended(self) -> Result<Self, Error> {
+        match self {
+            Self::SuspendPending => Ok(Self::Suspended),
+            _ => Err(Error::Failed),
+        }
+    }
+
+    /// State after an interrupt or platform specific event wakes up the suspended hart.
+    ///
+    /// The hart then returns to `STARTED` state with [`HartState::started`].
+    /// Returns [`Error::Failed`] if the hart is not suspended.
+    #[inline]
+    pub const fn resume(self) -> Result<Self, Error> {
+        match self {
+            Self::Suspended => Ok(Self::ResumePending),
+            _ => Err(Error::Failed),
+        }
+    }
+}
+
+impl core::fmt::Display for HartState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::Started => "STARTED",
+            Self::Stopped => "STOPPED",
+            Self::StartPending => "START_PENDING",
+            Self::StopPending => "STOP_PENDING",
+            Self::Suspended => "SUSPENDED",
+            Self::SuspendPending => "SUSPEND_PENDING",
+            Self::ResumePending => "RESUME_PENDING",
+        })
+    }
+}
+
+/// HSM state of one hart that can be transitioned concurrently by multiple harts.
+///
+/// Every transition is a compare-and-swap on the current state, allowing only legal
+/// transitions of [`HartState`]. When two harts race to start the same stopped hart,
+/// exactly one of them moves it to `START_PENDING`, while the other observes the new
+/// state and gets [`Error::InvalidParam`].
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::{binary::{Error, SbiRet}, hsm::{AtomicHartState, HartState}};
+/// static HART_1: AtomicHartState = AtomicHartState::new(HartState::Stopped);
+///
+/// // hart 0 starts hart 1
+/// assert_eq!(HART_1.start(), Ok(HartState::StartPending));
+/// // a racing start request fails
+/// assert_eq!(HART_1.start(), Err(Error::InvalidParam));
+/// // hart 1 is running in supervisor mode
+/// assert_eq!(HART_1.started(), Ok(HartState::Started));
+/// let ret = HART_1.start().map_or_else(SbiRet::from, |_| SbiRet::success(0));
+/// assert_eq!(ret, SbiRet::already_available());
+/// ```
+#[cfg(target_has_atomic = "ptr")]
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct AtomicHartState {
+    state: AtomicUsize,
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl AtomicHartState {
+    /// Creates a hart state cell with initial `state`.
+    #[inline]
+    pub const fn new(state: HartState) -> Self {
+        Self {
+            state: AtomicUsize::new(state.raw()),
+        }
+    }
+
+    /// Loads the current hart state.
+    #[inline]
+    pub fn load(&self) -> HartState {
+        match HartState::from_raw(self.state.load(Ordering::Acquire)) {
+            Some(state) => state,
+            None => unreachable!(),
+        }
+    }
+
+    /// Transitions the hart state with transition function `f`, returning the new state.
+    ///
+    /// `f` is called with the current state, and may be called again if the state is
+    /// changed concurrently. The state is not changed if `f` returns an error.
+    pub fn transition(
+        &self,
+        f: impl Fn(HartState) -> Result<HartState, Error>,
+    ) -> Result<HartState, Error> {
+        let mut current = self.load();
+        loop {
+            let next = f(current)?;
+            match self.state.compare_exchange_weak(
+                current.raw(),
+                next.raw(),
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return Ok(next),
+                Err(raw) => {
+                    current = match HartState::from_raw(raw) {
+                        Some(state) => state,
+                        None => unreachable!(),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Applies [`HartState::start`] on the target hart.
+    #[inline]
+    pub fn start(&self) -> Result<HartState, Error> {
+        self.transition(HartState::start)
+    }
+
+    /// Applies [`HartState::stop`] on the calling hart.
+    #[inline]
+    pub fn stop(&self) -> Result<HartState, Error> {
+        self.transition(HartState::stop)
+    }
+
+    /// Applies [`HartState::suspend`] on the calling hart.
+    #[inline]
+    pub fn suspend(&self) -> Result<HartState, Error> {
+        self.transition(HartState::suspend)
+    }
+
+    /// Applies [`HartState::started`] after the hart is started or resumed.
+    #[inline]
+    pub fn started(&self) -> Result<HartState, Error> {
+        self.transition(HartState::started)
+    }
+
+    /// Applies [`HartState::stopped`] after the hart is stopped.
+    #[inline]
+    pub fn stopped(&self) -> Result<HartState, Error> {
+        self.transition(HartState::stopped)
+    }
+
+    /// Applies [`HartState::suspended`] after the hart is suspended.
+    #[inline]
+    pub fn suspended(&self) -> Result<HartState, Error> {
+        self.transition(HartState::suspended)
+    }
+
+    /// Applies [`HartState::resume`] when the suspended hart wakes up.
+    #[inline]
+    pub fn resume(&self) -> Result<HartState, Error> {
+        self.transition(HartState::resume)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AtomicHartState, HartState, SuspendSupport, SuspendType};
+    use crate::binary::Error;
+
+    #[test]
+    fn hart_state_transitions() {
+        for raw in 0..7 {
+            assert_eq!(HartState::from_raw(raw).unwrap().raw(), raw);
+        }
+        assert_eq!(HartState::from_raw(7), None);
+        let all = [
+            HartState::Started,
+            HartState::Stopped,
+            HartState::StartPending,
+            HartState::StopPending,
+            HartState::Suspended,
+            HartState::SuspendPending,
+            HartState::ResumePending,
+        ];
+        for state in all {
+            let start = match state {
+                HartState::Stopped => Ok(HartState::StartPending),
+                HartState::Started => Err(Error::AlreadyAvailable),
+                _ => Err(Error::InvalidParam),
+            };
+            assert_eq!(state.start(), start);
+            if state != HartState::Started {
+                assert_eq!(state.stop(), Err(Error::Failed));
+                assert_eq!(state.suspend(), Err(Error::Failed));
+            }
+        }
+        // start, suspend, resume and stop
+        let state = HartState::Stopped.start().unwrap();
+        assert!(state.is_pending());
+        assert_eq!(state.stopped(), Err(Error::Failed));
+        let state = state.started().unwrap();
+        let state = state.suspend().and_then(HartState::suspended).unwrap();
+        assert_eq!(state, HartState::Suspended);
+        assert_eq!(state.started(), Err(Error::Failed));
+        let state = state.resume().and_then(HartState::started).unwrap();
+        let state = state.stop().and_then(HartState::stopped).unwrap();
+        assert_eq!(state, HartState::Stopped);
+        assert!(!state.is_pending());
+    }
+
+    #[test]
+    fn atomic_hart_state() {
+        let state = AtomicHartState::new(HartState::Stopped);
+        assert_eq!(state.stop(), Err(Error::Failed));
+        assert_eq!(state.start(), Ok(HartState::StartPending));
+        assert_eq!(state.start(), Err(Error::InvalidParam));
+        assert_eq!(state.load(), HartState::StartPending);
+        assert_eq!(state.started(), Ok(HartState::Started));
+        assert_eq!(state.start(), Err(Error::AlreadyAvailable));
+        assert_eq!(state.suspend(), Ok(HartState::SuspendPending));
+        assert_eq!(state.suspended(), Ok(HartState::Suspended));
+        assert_eq!(state.resume(), Ok(HartState::ResumePending));
+        assert_eq!(state.started(), Ok(HartState::Started));
+        assert_eq!(state.stop(), Ok(HartState::StopPending));
+        assert_eq!(state.stopped(), Ok(HartState::Stopped));
+        // failed transitions keep current state
+        assert_eq!(state.stopped(), Err(Error::Failed));
+        assert_eq!(state.load(), HartState::Stopped);
+    }
+
+    #[test]
+    fn suspend_type() {
+        let cases = [
+            (0x0000_0000, SuspendType::DefaultRetentive),
+            (0x0000_0001, SuspendType::Reserved(0x0000_0001)),
+            (0x0fff_ffff, SuspendType::Reserved(0x0fff_ffff)),
+            (0x1000_0000, SuspendType::PlatformRetentive(0x1000_0000)),
+            (0x7fff_ffff, SuspendType::PlatformRetentive(0x7fff_ffff)),
+            (0x8000_0000, SuspendType::DefaultNonRetentive),
+            (0x8000_0001, SuspendType::Reserved(0x8000_0001)),
+            (0x8fff_ffff, SuspendType::Reserved(0x8fff_ffff)),
+            (0x9000_0000, SuspendType::PlatformNonRetentive(0x9000_0000)),
+            (0xffff_ffff, SuspendType::PlatformNonRetentive(0xffff_ffff)),
+        ];
+        for (raw, suspend_type) in cases {
+            assert_eq!(SuspendType::from_raw(raw), suspend_type);
+            assert_eq!(suspend_type.raw(), raw);
+            assert_eq!(suspend_type.is_retentive(), raw < 0x8000_0000);
+        }
+        assert!(!SuspendType::DefaultRetentive.has_resume_addr());
+        assert!(!SuspendType::PlatformRetentive(0x1000_0000).has_resume_addr());
+        assert!(SuspendType::DefaultNonRetentive.has_resume_addr());
+        assert!(!SuspendType::Reserved(0x8000_0001).has_resume_addr());
+        assert!(SuspendType::PlatformRetentive(0x1000_0000).is_platform());
+        let platform = SuspendType::PlatformNonRetentive(0x9000_0000);
+        assert_eq!(
+            SuspendType::Reserved(0x8000_0001).check(|_| unreachable!()),
+            Err(Error::InvalidParam)
+        );
+        assert_eq!(platform.check(|_| SuspendSupport::Supported), Ok(platform));
+        assert_eq!(
+            platform.check(|_| SuspendSupport::MissingDependencies),
+            Err(Error::NotSupported)
+        );
+        assert_eq!(
+            platform.check(|_| SuspendSupport::Unimplemented),
+            Err(Error::InvalidParam)
+        );
+        assert_eq!(
+            SuspendType::DefaultRetentive.check(|_| SuspendSupport::Unimplemented),
+            Err(Error::NotSupported)
+        );
+    }
+}
@@ -46,6 +46,14 @@ pub mod cppc;
 pub mod nacl;
 // §16
 pub mod sta;
+// §17
+pub mod sse;
+// §18
+pub mod fwft;
+// §19
+pub mod dbtr;
+// §20
+pub mod mpxy;
 
 /// Converts SBI EID from str.
 const fn eid_from_str(name: &str) -> i32 {
@@ -84,6 +92,11 @@ mod tests {
         const_assert_eq!(-7, RET_ERR_ALREADY_STARTED as isize);
         const_assert_eq!(-8, RET_ERR_ALREADY_STOPPED as isize);
         const_assert_eq!(-9, RET_ERR_NO_SHMEM as isize);
+        const_assert_eq!(-10, RET_ERR_INVALID_STATE as isize);
+        const_assert_eq!(-11, RET_ERR_BAD_RANGE as isize);
+        const_assert_eq!(-12, RET_ERR_TIMEOUT as isize);
+        const_assert_eq!(-13, RET_ERR_IO as isize);
+        const_assert_eq!(-14, RET_ERR_DENIED_LOCKED as isize);
     }
     // §4
     #[test]
@@ -159,6 +172,20 @@ mod tests {
         const_assert_eq!(4, hart_state::SUSPENDED);
         const_assert_eq!(5, hart_state::SUSPEND_PENDING);
         const_assert_eq!(6, hart_state::RESUME_PENDING);
+        const_assert_eq!(hart_state::STARTED, HartState::Started as usize);
+        const_assert_eq!(hart_state::STOPPED, HartState::Stopped as usize);
+        const_assert_eq!(hart_state::START_PENDING, HartState::StartPending as usize);
+        const_assert_eq!(hart_state::STOP_PENDING, HartState::StopPending as usize);
+        const_assert_eq!(hart_state::SUSPENDED, HartState::Suspended as usize);
+        const_assert_eq!(
+            hart_state::SUSPEND_PENDING,
+            HartState::SuspendPending as usize
+        );
+        const_assert_eq!(
+            hart_state::RESUME_PENDING,
+            HartState::ResumePending as usize
+        );
+        assert_eq_size!(AtomicHartState, usize);
         const_assert_eq!(0x0000_0000, suspend_type::RETENTIVE);
         const_assert_eq!(0x8000_0000, suspend_type::NON_RETENTIVE);
         const_assert_eq!(0, HART_START);
@@ -246,7 +273,37 @@ mod tests {
         const_assert_eq!(19, firmware_event::HFENCE_VVMA_RECEIVED);
         const_assert_eq!(20, firmware_event::HFENCE_VVMA_ASID_SENT);
         const_assert_eq!(21, firmware_event::HFENCE_VVMA_ASID_RECEIVED);
+        const_assert_eq!(256, firmware_event::IMPL_START);
+        const_assert_eq!(65534, firmware_event::IMPL_END);
         const_assert_eq!(65535, firmware_event::PLATFORM);
+
+        const_assert_eq!(1 << 0, ConfigFlags::SKIP_MATCH.raw());
+        const_assert_eq!(1 << 1, ConfigFlags::CLEAR_VALUE.raw());
+        const_assert_eq!(1 << 2, ConfigFlags::AUTO_START.raw());
+        const_assert_eq!(1 << 3, ConfigFlags::SET_VUINH.raw());
+        const_assert_eq!(1 << 4, ConfigFlags::SET_VSINH.raw());
+        const_assert_eq!(1 << 5, ConfigFlags::SET_UINH.raw());
+        const_assert_eq!(1 << 6, ConfigFlags::SET_SINH.raw());
+        const_assert_eq!(1 << 7, ConfigFlags::SET_MINH.raw());
+        const_assert_eq!(1 << 0, StartFlags::INIT_VALUE.raw());
+        const_assert_eq!(1 << 1, StartFlags::INIT_SNAPSHOT.raw());
+        const_assert_eq!(1 << 0, StopFlags::RESET.raw());
+        const_assert_eq!(1 << 1, StopFlags::TAKE_SNAPSHOT.raw());
+        assert_eq_size!(ConfigFlags, usize);
+        assert_eq_size!(StartFlags, usize);
+        assert_eq_size!(StopFlags, usize);
+        assert_eq_size!(EventIdx, usize);
+        assert_eq_size!(CounterInfo, usize);
+        assert_eq_size!(SnapshotShmem, [u8; 4096]);
+        const_assert_eq!(4096, core::mem::align_of::<SnapshotShmem>());
+        assert_fields!(SnapshotShmem: counter_overflow_bitmap, counter_values);
+        const_assert_eq!(
+            0x0000,
+            core::mem::offset_of!(SnapshotShmem, counter_overflow_bitmap)
+        );
+        const_assert_eq!(0x0008, core::mem::offset_of!(SnapshotShmem, counter_values));
+        const_assert_eq!(64, SNAPSHOT_COUNTERS);
+        assert_eq_size!(CounterMask, [usize; 2]);
     }
     // §12
     #[test]
@@ -263,6 +320,8 @@ mod tests {
         use crate::susp::*;
         const_assert_eq!(0x53555350, EID_SUSP);
         const_assert_eq!(0, SUSPEND);
+        const_assert_eq!(0, sleep_type::SUSPEND_TO_RAM);
+        const_assert_eq!(0x8000_0000, sleep_type::PLATFORM_START);
     }
     // §14
     #[test]
@@ -273,6 +332,28 @@ mod tests {
         const_assert_eq!(1, READ);
         const_assert_eq!(2, READ_HI);
         const_assert_eq!(3, WRITE);
+        const_assert_eq!(0x0000_0000, reg_id::HIGHEST_PERFORMANCE);
+        const_assert_eq!(0x0000_0001, reg_id::NOMINAL_PERFORMANCE);
+        const_assert_eq!(0x0000_0002, reg_id::LOWEST_NONLINEAR_PERFORMANCE);
+        const_assert_eq!(0x0000_0003, reg_id::LOWEST_PERFORMANCE);
+        const_assert_eq!(0x0000_0004, reg_id::GUARANTEED_PERFORMANCE);
+        const_assert_eq!(0x0000_0005, reg_id::DESIRED_PERFORMANCE);
+        const_assert_eq!(0x0000_0006, reg_id::MINIMUM_PERFORMANCE);
+        const_assert_eq!(0x0000_0007, reg_id::MAXIMUM_PERFORMANCE);
+        const_assert_eq!(0x0000_0008, reg_id::PERFORMANCE_REDUCTION_TOLERANCE);
+        const_assert_eq!(0x0000_0009, reg_id::TIME_WINDOW);
+        const_assert_eq!(0x0000_000A, reg_id::COUNTER_WRAPAROUND_TIME);
+        const_assert_eq!(0x0000_000B, reg_id::REFERENCE_PERFORMANCE_COUNTER);
+        const_assert_eq!(0x0000_000C, reg_id::DELIVERED_PERFORMANCE_COUNTER);
+        const_assert_eq!(0x0000_000D, reg_id::PERFORMANCE_LIMITED);
+        const_assert_eq!(0x0000_000E, reg_id::CPPC_ENABLE);
+        const_assert_eq!(0x0000_000F, reg_id::AUTONOMOUS_SELECTION_ENABLE);
+        const_assert_eq!(0x0000_0010, reg_id::AUTONOMOUS_ACTIVITY_WINDOW);
+        const_assert_eq!(0x0000_0011, reg_id::ENERGY_PERFORMANCE_PREFERENCE);
+        const_assert_eq!(0x0000_0012, reg_id::REFERENCE_PERFORMANCE);
+        const_assert_eq!(0x0000_0013, reg_id::LOWEST_FREQUENCY);
+        const_assert_eq!(0x0000_0014, reg_id::NOMINAL_FREQUENCY);
+        const_assert_eq!(0x8000_0000, reg_id::TRANSITION_LATENCY);
     }
     // §15
     #[test]
@@ -307,6 +388,43 @@ mod tests {
                 const_assert_eq!(shmem_size::NATIVE, shmem_size::RV128);
             }
         }
+
+        const_assert_eq!(0x0000, shmem_offset::SRET_CONTEXT);
+        const_assert_eq!(0x0200, shmem_offset::AUTOSWAP_CONTEXT);
+        const_assert_eq!(0x0800, shmem_offset::HFENCE_ENTRIES);
+        const_assert_eq!(0x0F80, shmem_offset::CSR_DIRTY_BITMAP);
+        const_assert_eq!(0x1000, shmem_offset::CSR_SPACE);
+        const_assert_eq!(1024, CSR_SLOTS);
+        const_assert_eq!(0, hfence_type::GVMA);
+        const_assert_eq!(1, hfence_type::GVMA_ALL);
+        const_assert_eq!(2, hfence_type::GVMA_VMID);
+        const_assert_eq!(3, hfence_type::GVMA_VMID_ALL);
+        const_assert_eq!(4, hfence_type::VVMA);
+        const_assert_eq!(5, hfence_type::VVMA_ALL);
+        const_assert_eq!(6, hfence_type::VVMA_ASID);
+        const_assert_eq!(7, hfence_type::VVMA_ASID_ALL);
+        const_assert_eq!(12, HFENCE_ORDER_BASE);
+        assert_eq_size!(HfenceEntry<u32>, [u32; 4]);
+        assert_eq_size!(HfenceEntry<u64>, [u64; 4]);
+        assert_eq_size!(<u32 as Xlen>::HfenceEntries, [u8; 0x780]);
+        assert_eq_size!(<u64 as Xlen>::HfenceEntries, [u8; 0x780]);
+        assert_eq_size!(<usize as Xlen>::HfenceEntries, [u8; 0x780]);
+        assert_eq_size!(ShmemRv32, [u8; shmem_size::RV32]);
+        assert_eq_size!(ShmemRv64, [u8; shmem_size::RV64]);
+        assert_eq_size!(ShmemNative, [u8; shmem_size::NATIVE]);
+        const_assert_eq!(4096, core::mem::align_of::<ShmemRv32>());
+        const_assert_eq!(
+            shmem_offset::AUTOSWAP_CONTEXT,
+            core::mem::offset_of!(ShmemRv64, autoswap_context)
+        );
+        const_assert_eq!(
+            shmem_offset::HFENCE_ENTRIES,
+            core::mem::offset_of!(ShmemRv64, hfence_entries)
+        );
+        const_assert_eq!(
+            shmem_offset::HFENCE_ENTRIES,
+            core::mem::offset_of!(ShmemRv32, hfence_entries)
+        );
     }
     // §16
     #[test]
@@ -315,4 +433,140 @@ mod tests {
         const_assert_eq!(0x535441, EID_STA);
         const_assert_eq!(0, SET_SHMEM);
     }
+    // §17
+    #[test]
+    fn test_sse() {
+        use crate::sse::*;
+        const_assert_eq!(0x535345, EID_SSE);
+        const_assert_eq!(0, READ_ATTRS);
+        const_assert_eq!(1, WRITE_ATTRS);
+        const_assert_eq!(2, REGISTER);
+        const_assert_eq!(3, UNREGISTER);
+        const_assert_eq!(4, ENABLE);
+        const_assert_eq!(5, DISABLE);
+        const_assert_eq!(6, COMPLETE);
+        const_assert_eq!(7, INJECT);
+        const_assert_eq!(8, HART_UNMASK);
+        const_assert_eq!(9, HART_MASK);
+
+        const_assert_eq!(0x0000_0000, event_id::LOCAL_HIGH_PRIO_RAS);
+        const_assert_eq!(0x0000_0001, event_id::LOCAL_DOUBLE_TRAP);
+        const_assert_eq!(0x0000_8000, event_id::GLOBAL_HIGH_PRIO_RAS);
+        const_assert_eq!(0x0001_0000, event_id::LOCAL_PMU_OVERFLOW);
+        const_assert_eq!(0x0010_0000, event_id::LOCAL_LOW_PRIO_RAS);
+        const_assert_eq!(0x0010_8000, event_id::GLOBAL_LOW_PRIO_RAS);
+        const_assert_eq!(0xffff_0000, event_id::LOCAL_SOFTWARE_INJECTED);
+        const_assert_eq!(0xffff_8000, event_id::GLOBAL_SOFTWARE_INJECTED);
+        const_assert_eq!(0x8000, event_id::GLOBAL);
+        const_assert_eq!(0x4000, event_id::PLATFORM);
+
+        const_assert_eq!(0, attr_id::STATUS);
+        const_assert_eq!(1, attr_id::PRIORITY);
+        const_assert_eq!(2, attr_id::CONFIG);
+        const_assert_eq!(3, attr_id::PREFERRED_HART);
+        const_assert_eq!(4, attr_id::ENTRY_PC);
+        const_assert_eq!(5, attr_id::ENTRY_ARG);
+        const_assert_eq!(6, attr_id::INTERRUPTED_SEPC);
+        const_assert_eq!(7, attr_id::INTERRUPTED_FLAGS);
+        const_assert_eq!(8, attr_id::INTERRUPTED_A6);
+        const_assert_eq!(9, attr_id::INTERRUPTED_A7);
+
+        const_assert_eq!(0, EventState::Unused as usize);
+        const_assert_eq!(1, EventState::Registered as usize);
+        const_assert_eq!(2, EventState::Enabled as usize);
+        const_assert_eq!(3, EventState::Running as usize);
+    }
+    // §18
+    #[test]
+    fn test_fwft() {
+        use crate::fwft::*;
+        const_assert_eq!(0x46574654, EID_FWFT);
+        const_assert_eq!(0, SET);
+        const_assert_eq!(1, GET);
+
+        const_assert_eq!(0, feature_type::MISALIGNED_EXC_DELEG);
+        const_assert_eq!(1, feature_type::LANDING_PAD);
+        const_assert_eq!(2, feature_type::SHADOW_STACK);
+        const_assert_eq!(3, feature_type::DOUBLE_TRAP);
+        const_assert_eq!(4, feature_type::PTE_AD_HW_UPDATING);
+        const_assert_eq!(5, feature_type::POINTER_MASKING_PMLEN);
+        const_assert_eq!(0x8000_0000, feature_type::GLOBAL);
+        const_assert_eq!(0x4000_0000, feature_type::PLATFORM);
+
+        const_assert_eq!(1, flags::LOCK);
+    }
+    // §19
+    #[test]
+    fn test_dbtr() {
+        use crate::dbtr::*;
+        const_assert_eq!(0x44425452, EID_DBTR);
+        const_assert_eq!(0, NUM_TRIGGERS);
+        const_assert_eq!(1, SET_SHMEM);
+        const_assert_eq!(2, READ_TRIGGERS);
+        const_assert_eq!(3, INSTALL_TRIGGERS);
+        const_assert_eq!(4, UPDATE_TRIGGERS);
+        const_assert_eq!(5, UNINSTALL_TRIGGERS);
+        const_assert_eq!(6, ENABLE_TRIGGERS);
+        const_assert_eq!(7, DISABLE_TRIGGERS);
+
+        const_assert_eq!(0, trigger_type::NONE);
+        const_assert_eq!(1, trigger_type::LEGACY);
+        const_assert_eq!(2, trigger_type::MCONTROL);
+        const_assert_eq!(3, trigger_type::ICOUNT);
+        const_assert_eq!(4, trigger_type::ITRIGGER);
+        const_assert_eq!(5, trigger_type::ETRIGGER);
+        const_assert_eq!(6, trigger_type::MCONTROL6);
+        const_assert_eq!(7, trigger_type::TMEXTTRIGGER);
+        const_assert_eq!(15, trigger_type::DISABLED);
+
+        assert_eq_align!(ShmemEntry, usize);
+        assert_eq_size!(ShmemEntry, [usize; 4]);
+        assert_fields!(ShmemEntry: tdata1, tdata2, tdata3);
+        assert_eq_size!(TriggerState, usize);
+        assert_eq_size!(Tdata1, usize);
+    }
+    // §20
+    #[test]
+    fn test_mpxy() {
+        use crate::mpxy::*;
+        const_assert_eq!(0x4D505859, EID_MPXY);
+        const_assert_eq!(0, GET_SHMEM_SIZE);
+        const_assert_eq!(1, SET_SHMEM);
+        const_assert_eq!(2, GET_CHANNEL_IDS);
+        const_assert_eq!(3, READ_ATTRIBUTES);
+        const_assert_eq!(4, WRITE_ATTRIBUTES);
+        const_assert_eq!(5, SEND_MESSAGE_WITH_RESPONSE);
+        const_assert_eq!(6, SEND_MESSAGE_WITHOUT_RESPONSE);
+        const_assert_eq!(7, GET_NOTIFICATION_EVENTS);
+
+        const_assert_eq!(0, protocol_id::RPMI);
+        const_assert_eq!(0x8000_0000, protocol_id::VENDOR_START);
+
+        const_assert_eq!(0x0, attr_id::MSG_PROT_ID);
+        const_assert_eq!(0x1, attr_id::MSG_PROT_VERSION);
+        const_assert_eq!(0x2, attr_id::MSG_MAX_LEN);
+        const_assert_eq!(0x3, attr_id::MSG_SEND_TIMEOUT);
+        const_assert_eq!(0x4, attr_id::MSG_COMPLETION_TIMEOUT);
+        const_assert_eq!(0x5, attr_id::CHANNEL_CAPABILITY);
+        const_assert_eq!(0x6, attr_id::SSE_EVENT_ID);
+        const_assert_eq!(0x7, attr_id::MSI_CONTROL);
+        const_assert_eq!(0x8, attr_id::MSI_ADDR_LO);
+        const_assert_eq!(0x9, attr_id::MSI_ADDR_HI);
+        const_assert_eq!(0xA, attr_id::MSI_DATA);
+        const_assert_eq!(0xB, attr_id::EVENTS_STATE_CONTROL);
+        const_assert_eq!(0x8000_0000, attr_id::PROTOCOL_START);
+
+        const_assert_eq!(1 << 0, capability::MSI);
+        const_assert_eq!(1 << 1, capability::SSE);
+        const_assert_eq!(1 << 2, capability::EVENTS_STATE);
+        const_assert_eq!(1 << 3, capability::SEND_WITH_RESPONSE);
+        const_assert_eq!(1 << 4, capability::SEND_WITHOUT_RESPONSE);
+        const_assert_eq!(1 << 5, capability::GET_NOTIFICATIONS);
+
+        assert_eq_size!(ChannelIdsHeader, [u32; 2]);
+        assert_fields!(ChannelIdsHeader: remaining, returned);
+        assert_eq_size!(NotificationEventsHeader, [u32; 4]);
+        assert_fields!(NotificationEventsHeader: remaining, returned, lost, reserved);
+        assert_eq_size!(ProtocolVersion, u32);
+    }
 }
@@ -0,0 +1,318 @@
+//! Chapter 20. Message Proxy Extension (EID #0x4D505859 "MPXY").
+
+/// Extension ID for Message Proxy Extension.
+pub const EID_MPXY: usize = crate::eid_from_str("MPXY") as _;
+pub use fid::*;
+
+/// Declared in §20.13.
+mod fid {
+    /// Function ID to get the size of shared memory for message proxy on the calling hart.
+    ///
+    /// Declared in §20.5.
+    pub const GET_SHMEM_SIZE: usize = 0;
+    /// Function ID to set and enable the shared memory for message proxy on the calling hart.
+    ///
+    /// Declared in §20.6.
+    pub const SET_SHMEM: usize = 1;
+    /// Function ID to get channel IDs of the message channels accessible to supervisor software.
+    ///
+    /// Declared in §20.7.
+    pub const GET_CHANNEL_IDS: usize = 2;
+    /// Function ID to read message channel attributes.
+    ///
+    /// Declared in §20.8.
+    pub const READ_ATTRIBUTES: usize = 3;
+    /// Function ID to write message channel attributes.
+    ///
+    /// Declared in §20.9.
+    pub const WRITE_ATTRIBUTES: usize = 4;
+    /// Function ID to send a message to a message channel and wait for its response.
+    ///
+    /// Declared in §20.10.
+    pub const SEND_MESSAGE_WITH_RESPONSE: usize = 5;
+    /// Function ID to send a message to a message channel without waiting for response.
+    ///
+    /// Declared in §20.11.
+    pub const SEND_MESSAGE_WITHOUT_RESPONSE: usize = 6;
+    /// Function ID to get notification events of a message channel.
+    ///
+    /// Declared in §20.12.
+    pub const GET_NOTIFICATION_EVENTS: usize = 7;
+}
+
+/// Message protocol IDs.
+///
+/// Message protocol IDs from `0x8000_0000` to `0xFFFF_FFFF` are vendor-specific;
+/// other IDs not listed here are reserved.
+///
+/// Declared in §20.1.
+pub mod protocol_id {
+    /// RISC-V Platform Management Interface (RPMI).
+    pub const RPMI: u32 = 0x0000_0000;
+    /// First vendor-specific message protocol ID.
+    pub const VENDOR_START: u32 = 0x8000_0000;
+}
+
+/// Standard message channel attribute IDs.
+///
+/// Attribute IDs from `0x8000_0000` to `0xFFFF_FFFF` are specific to the message protocol;
+/// other IDs not listed here are reserved.
+///
+/// Declared in §20.3.
+pub mod attr_id {
+    /// Message protocol ID of the channel, read-only.
+    pub const MSG_PROT_ID: u32 = 0x0000_0000;
+    /// Message protocol version of the channel, read-only.
+    pub const MSG_PROT_VERSION: u32 = 0x0000_0001;
+    /// Maximum message data length in bytes, read-only.
+    pub const MSG_MAX_LEN: u32 = 0x0000_0002;
+    /// Timeout in microseconds for sending a message, read-only.
+    pub const MSG_SEND_TIMEOUT: u32 = 0x0000_0003;
+    /// Timeout in microseconds for the completion of a message, read-only.
+    pub const MSG_COMPLETION_TIMEOUT: u32 = 0x0000_0004;
+    /// Channel capabilities as defined in module `capability`, read-only.
+    pub const CHANNEL_CAPABILITY: u32 = 0x0000_0005;
+    /// Software event ID for notifications of the channel, read-only.
+    pub const SSE_EVENT_ID: u32 = 0x0000_0006;
+    /// Enable or disable MSI for notifications of the channel.
+    pub const MSI_CONTROL: u32 = 0x0000_0007;
+    /// Low part of the MSI address for notifications of the channel.
+    pub const MSI_ADDR_LO: u32 = 0x0000_0008;
+    /// High part of the MSI address for notifications of the channel.
+    pub const MSI_ADDR_HI: u32 = 0x0000_0009;
+    /// MSI data for notifications of the channel.
+    pub const MSI_DATA: u32 = 0x0000_000A;
+    /// Enable or disable reporting of events state data in notifications.
+    pub const EVENTS_STATE_CONTROL: u32 = 0x0000_000B;
+    /// First message protocol specific attribute ID.
+    pub const PROTOCOL_START: u32 = 0x8000_0000;
+}
+
+/// Message channel capability bits in the `CHANNEL_CAPABILITY` attribute.
+///
+/// Declared in §20.3.
+pub mod capability {
+    /// The channel supports MSI for notifications.
+    pub const MSI: u32 = 1 << 0;
+    /// The channel supports software events for notifications.
+    pub const SSE: u32 = 1 << 1;
+    /// The channel supports events state data in notifications.
+    pub const EVENTS_STATE: u32 = 1 << 2;
+    /// The channel supports the send message with response function.
+    pub const SEND_WITH_RESPONSE: u32 = 1 << 3;
+    /// The channel supports the send message without response function.
+    pub const SEND_WITHOUT_RESPONSE: u32 = 1 << 4;
+    /// The channel supports the get notification events function.
+    pub const GET_NOTIFICATIONS: u32 = 1 << 5;
+}
+
+/// Flags for the message proxy set shared memory function.
+///
+/// Declared in §20.6.
+pub mod shmem_flags {
+    /// Mask of the shared memory operation field.
+    pub const OP_MASK: usize = 0b11;
+    /// Overwrite the previous shared memory.
+    pub const OVERWRITE: usize = 0b00;
+    /// Overwrite the previous shared memory, and return its address in the new shared memory.
+    pub const OVERWRITE_RETURN: usize = 0b01;
+}
+
+/// Message protocol ID classified by the message protocol ID space.
+///
+/// Declared in §20.1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MessageProtocol {
+    /// RISC-V Platform Management Interface (RPMI).
+    Rpmi,
+    /// Reserved message protocol ID for future use.
+    Reserved(u32),
+    /// Vendor-specific message protocol ID.
+    Vendor(u32),
+}
+
+impl MessageProtocol {
+    /// Classifies a raw message protocol ID.
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        match raw {
+            protocol_id::RPMI => Self::Rpmi,
+            protocol_id::VENDOR_START.. => Self::Vendor(raw),
+            _ => Self::Reserved(raw),
+        }
+    }
+
+    /// Returns the raw message protocol ID.
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        match self {
+            Self::Rpmi => protocol_id::RPMI,
+            Self::Reserved(raw) | Self::Vendor(raw) => raw,
+        }
+    }
+}
+
+/// Message protocol version in the `MSG_PROT_VERSION` attribute.
+///
+/// Declared in §20.3.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ProtocolVersion {
+    raw: u32,
+}
+
+impl ProtocolVersion {
+    /// Converts raw attribute value into `ProtocolVersion` structure.
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        Self { raw }
+    }
+
+    /// Creates a message protocol version from major and minor version.
+    #[inline]
+    pub const fn new(major: u16, minor: u16) -> Self {
+        Self {
+            raw: ((major as u32) << 16) | minor as u32,
+        }
+    }
+
+    /// Returns the raw attribute value.
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        self.raw
+    }
+
+    /// Reads major version of the message protocol.
+    #[inline]
+    pub const fn major(self) -> u16 {
+        (self.raw >> 16) as u16
+    }
+
+    /// Reads minor version of the message protocol.
+    #[inline]
+    pub const fn minor(self) -> u16 {
+        self.raw as u16
+    }
+}
+
+impl core::fmt::Display for ProtocolVersion {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}.{}", self.major(), self.minor())
+    }
+}
+
+/// Message channel attribute ID classified by the attribute ID space.
+///
+/// Declared in §20.3.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AttributeId {
+    /// Standard attribute defined by the SBI specification.
+    Standard(u32),
+    /// Reserved attribute ID for future use.
+    Reserved(u32),
+    /// Attribute ID specific to the message protocol of the channel.
+    Protocol(u32),
+}
+
+impl AttributeId {
+    /// Classifies a raw attribute ID.
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        match raw {
+            attr_id::MSG_PROT_ID..=attr_id::EVENTS_STATE_CONTROL => Self::Standard(raw),
+            attr_id::PROTOCOL_START.. => Self::Protocol(raw),
+            _ => Self::Reserved(raw),
+        }
+    }
+
+    /// Returns the raw attribute ID.
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        match self {
+            Self::Standard(raw) | Self::Reserved(raw) | Self::Protocol(raw) => raw,
+        }
+    }
+
+    /// Returns `true` if supervisor software is allowed to write this standard attribute.
+    ///
+    /// Protocol specific attributes are always regarded as writable here, and
+    /// reserved attributes are never writable.
+    #[inline]
+    pub const fn is_writable(self) -> bool {
+        match self {
+            Self::Standard(raw) => raw >= attr_id::MSI_CONTROL,
+            Self::Reserved(_) => false,
+            Self::Protocol(_) => true,
+        }
+    }
+}
+
+/// Header of the get channel IDs function output in shared memory.
+///
+/// The header is followed by `returned` channel IDs of type `u32`.
+///
+/// Declared in §20.7.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct ChannelIdsHeader {
+    /// Number of remaining channel IDs after the returned ones.
+    pub remaining: u32,
+    /// Number of channel IDs returned in shared memory.
+    pub returned: u32,
+}
+
+/// Header of the get notification events function output in shared memory.
+///
+/// The header is followed by the notification events data.
+///
+/// Declared in §20.12.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct NotificationEventsHeader {
+    /// Number of remaining notification events.
+    pub remaining: u32,
+    /// Number of notification events returned in shared memory.
+    pub returned: u32,
+    /// Number of notification events lost due to insufficient space.
+    pub lost: u32,
+    /// Reserved for future use.
+    pub reserved: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AttributeId, MessageProtocol, ProtocolVersion};
+
+    #[test]
+    fn message_protocol() {
+        assert_eq!(MessageProtocol::from_raw(0), MessageProtocol::Rpmi);
+        assert_eq!(MessageProtocol::from_raw(1), MessageProtocol::Reserved(1));
+        assert_eq!(
+            MessageProtocol::from_raw(0x7fff_ffff),
+            MessageProtocol::Reserved(0x7fff_ffff)
+        );
+        assert_eq!(
+            MessageProtocol::from_raw(0x8000_0000),
+            MessageProtocol::Vendor(0x8000_0000)
+        );
+        assert_eq!(MessageProtocol::Vendor(0x8000_0001).raw(), 0x8000_0001);
+        let version = ProtocolVersion::new(1, 2);
+        assert_eq!(version.raw(), 0x0001_0002);
+        assert_eq!((version.major(), version.minor()), (1, 2));
+    }
+
+    #[test]
+    fn attribute_id() {
+        assert_eq!(AttributeId::from_raw(0), AttributeId::Standard(0));
+        assert_eq!(AttributeId::from_raw(0xb), AttributeId::Standard(0xb));
+        assert_eq!(AttributeId::from_raw(0xc), AttributeId::Reserved(0xc));
+        assert_eq!(
+            AttributeId::from_raw(0x8000_0000),
+            AttributeId::Protocol(0x8000_0000)
+        );
+        assert!(!AttributeId::from_raw(5).is_writable());
+        assert!(AttributeId::from_raw(7).is_writable());
+        assert!(!AttributeId::from_raw(0xc).is_writable());
+    }
+}
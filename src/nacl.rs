@@ -5,6 +5,9 @@ pub const EID_NACL: usize = crate::eid_from_str("NACL") as _;
 
 pub use fid::*;
 
+use crate::binary::Error;
+use core::mem::size_of;
+
 /// Declared in § 15.15.
 mod fid {
     /// Function ID to probe a nested acceleration feature.
@@ -73,3 +76,889 @@ pub mod shmem_size {
     /// Size of NACL shared memory on RV128 platforms.
     pub const RV128: usize = 4096 + 1024 * size_of::<u128>();
 }
+
+/// Byte offsets of regions in the NACL shared memory.
+///
+/// The scratch space takes the first 4096 bytes, and the CSR space follows it.
+///
+/// Declared in §15.
+pub mod shmem_offset {
+    /// Offset of the nested SRET context in scratch space.
+    pub const SRET_CONTEXT: usize = 0x0000;
+    /// Offset of the nested autoswap context in scratch space.
+    pub const AUTOSWAP_CONTEXT: usize = 0x0200;
+    /// Offset of the nested HFENCE entries in scratch space.
+    pub const HFENCE_ENTRIES: usize = 0x0800;
+    /// Offset of the nested CSR dirty bitmap in scratch space.
+    pub const CSR_DIRTY_BITMAP: usize = 0x0F80;
+    /// Offset of the CSR space.
+    pub const CSR_SPACE: usize = 0x1000;
+}
+
+/// Number of `XLEN`-wide CSR slots in the CSR space of NACL shared memory.
+pub const CSR_SLOTS: usize = 1024;
+
+/// Returns the CSR space slot index of a CSR number.
+///
+/// The CSR space holds hypervisor and virtual supervisor CSRs, i.e. CSRs with bits `[9:8]`
+/// of the CSR number equal to `0b10`, at slot index `((csr & 0xc00) >> 2) | (csr & 0xff)`.
+/// Returns `None` for any other CSR number.
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::nacl::{csr_index, csr_num};
+/// // hstatus
+/// assert_eq!(csr_index(0x600), Some(0x100));
+/// assert_eq!(csr_num(0x100), Some(0x600));
+/// // sstatus is not a hypervisor or virtual supervisor CSR
+/// assert_eq!(csr_index(0x100), None);
+/// ```
+#[inline]
+pub const fn csr_index(csr_num: usize) -> Option<usize> {
+    if csr_num > 0xfff || csr_num & 0x300 != 0x200 {
+        return None;
+    }
+    Some(((csr_num & 0xc00) >> 2) | (csr_num & 0xff))
+}
+
+/// Returns the CSR number of a CSR space slot index, or `None` if the index is out of range.
+///
+/// This is the inverse of [`csr_index`].
+#[inline]
+pub const fn csr_num(index: usize) -> Option<usize> {
+    if index >= CSR_SLOTS {
+        return None;
+    }
+    Some(((index & 0x300) << 2) | 0x200 | (index & 0xff))
+}
+
+/// `XLEN`-wide word in NACL shared memory.
+///
+/// This trait is implemented for `u32` on RV32, `u64` on RV64, and `usize` for the `XLEN`
+/// of the target this crate is compiled for. It cannot be implemented outside this crate.
+pub trait Xlen: Copy + Default + sealed::Sealed {
+    /// Width of the word in bits.
+    const BITS: u32;
+    /// Zero word.
+    const ZERO: Self;
+    /// Array of nested HFENCE entries filling the HFENCE region of scratch space.
+    type HfenceEntries: Copy + AsRef<[HfenceEntry<Self>]> + AsMut<[HfenceEntry<Self>]>;
+    /// Nested HFENCE entries with no pending entry.
+    const EMPTY_HFENCE_ENTRIES: Self::HfenceEntries;
+    /// Converts the word into `u64` with zero extension.
+    fn to_u64(self) -> u64;
+    /// Converts `u64` into the word, truncating bits wider than `XLEN`.
+    fn from_u64(value: u64) -> Self;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_xlen {
+    ($($ty:ty),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl Xlen for $ty {
+                const BITS: u32 = <$ty>::BITS;
+                const ZERO: Self = 0;
+                type HfenceEntries = [HfenceEntry<$ty>; HFENCE_SIZE / size_of::<HfenceEntry<$ty>>()];
+                const EMPTY_HFENCE_ENTRIES: Self::HfenceEntries =
+                    [HfenceEntry::EMPTY; HFENCE_SIZE / size_of::<HfenceEntry<$ty>>()];
+                #[inline]
+                fn to_u64(self) -> u64 {
+                    self as u64
+                }
+                #[inline]
+                fn from_u64(value: u64) -> Self {
+                    value as $ty
+                }
+            }
+        )*
+    };
+}
+
+impl_xlen!(u32, u64, usize);
+
+/// Size of the nested HFENCE entries region in scratch space.
+const HFENCE_SIZE: usize = shmem_offset::CSR_DIRTY_BITMAP - shmem_offset::HFENCE_ENTRIES;
+
+/// NACL shared memory layout with `XLEN`-wide CSR slots of type `T`.
+///
+/// Use [`ShmemRv32`] or [`ShmemRv64`] to access shared memory of a particular `XLEN`,
+/// or [`ShmemNative`] for the `XLEN` of the target this crate is compiled for.
+/// The shared memory is 4096-byte aligned, and its size matches the corresponding
+/// constant in module [`shmem_size`].
+///
+/// Writing a CSR through [`set_csr`](Self::set_csr) also marks it dirty in the CSR dirty
+/// bitmap, so that the SBI implementation would pick it up on the next CSR synchronization.
+///
+/// Declared in §15.
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::nacl::ShmemRv64;
+/// const HSTATUS: usize = 0x600;
+/// let mut shmem = ShmemRv64::default();
+/// shmem.set_csr(HSTATUS, 0x2_0000_0000).unwrap();
+/// assert_eq!(shmem.csr(HSTATUS), Ok(0x2_0000_0000));
+/// assert!(shmem.is_csr_dirty(HSTATUS));
+/// assert!(shmem.dirty_csrs().eq([HSTATUS]));
+/// ```
+#[repr(C, align(4096))]
+pub struct Shmem<T: Xlen> {
+    /// Nested SRET context in scratch space.
+    pub sret_context: [u8; 512],
+    /// Nested autoswap context in scratch space.
+    pub autoswap_context: [u8; 128],
+    reserved: [u8; 1408],
+    /// Nested HFENCE entries in scratch space.
+    pub hfence_entries: T::HfenceEntries,
+    csr_dirty_bitmap: [u8; 128],
+    csrs: [T; CSR_SLOTS],
+}
+
+/// NACL shared memory on RV32 platforms.
+pub type ShmemRv32 = Shmem<u32>;
+
+/// NACL shared memory on RV64 platforms.
+pub type ShmemRv64 = Shmem<u64>;
+
+/// NACL shared memory on platforms with `XLEN` of same width as the current platform.
+pub type ShmemNative = Shmem<usize>;
+
+impl<T: Xlen> Shmem<T> {
+    /// Creates a zeroed NACL shared memory with no dirty CSRs.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            sret_context: [0; 512],
+            autoswap_context: [0; 128],
+            reserved: [0; 1408],
+            hfence_entries: T::EMPTY_HFENCE_ENTRIES,
+            csr_dirty_bitmap: [0; 128],
+            csrs: [T::default(); CSR_SLOTS],
+        }
+    }
+}
+
+impl<T: Xlen> Shmem<T> {
+    /// Reads the value of a CSR in the CSR space.
+    ///
+    /// Returns [`Error::InvalidParam`] if the CSR is not in the CSR space.
+    #[inline]
+    pub fn csr(&self, csr_num: usize) -> Result<T, Error> {
+        match csr_index(csr_num) {
+            Some(idx) => Ok(self.csrs[idx]),
+            None => Err(Error::InvalidParam),
+        }
+    }
+
+    /// Writes the value of a CSR in the CSR space and marks it dirty.
+    ///
+    /// Returns [`Error::InvalidParam`] if the CSR is not in the CSR space.
+    #[inline]
+    pub fn set_csr(&mut self, csr_num: usize, value: T) -> Result<(), Error> {
+        match csr_index(csr_num) {
+            Some(idx) => {
+                self.csrs[idx] = value;
+                self.csr_dirty_bitmap[idx / 8] |= 1 << (idx % 8);
+                Ok(())
+            }
+            None => Err(Error::InvalidParam),
+        }
+    }
+
+    /// Returns `true` if the CSR is marked dirty, or `false` if it is not in the CSR space.
+    #[inline]
+    pub fn is_csr_dirty(&self, csr_num: usize) -> bool {
+        match csr_index(csr_num) {
+            Some(idx) => self.csr_dirty_bitmap[idx / 8] & (1 << (idx % 8)) != 0,
+            None => false,
+        }
+    }
+
+    /// Clears the dirty bit of a CSR, returning `true` if it was marked dirty.
+    #[inline]
+    pub fn clear_csr_dirty(&mut self, csr_num: usize) -> bool {
+        let dirty = self.is_csr_dirty(csr_num);
+        if let Some(idx) = csr_index(csr_num) {
+            self.csr_dirty_bitmap[idx / 8] &= !(1 << (idx % 8));
+        }
+        dirty
+    }
+
+    /// Clears the dirty bits of all CSRs.
+    #[inline]
+    pub fn clear_all_csr_dirty(&mut self) {
+        self.csr_dirty_bitmap = [0; 128];
+    }
+
+    /// Returns an iterator over the CSR numbers marked dirty, in ascending slot index order.
+    #[inline]
+    pub fn dirty_csrs(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..CSR_SLOTS)
+            .filter(|idx| self.csr_dirty_bitmap[idx / 8] & (1 << (idx % 8)) != 0)
+            .filter_map(csr_num)
+    }
+}
+
+impl<T: Xlen> Default for Shmem<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Xlen + core::fmt::Debug> core::fmt::Debug for Shmem<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Shmem")
+            .field("dirty_csrs", &DirtyCsrs { shmem: self })
+            .finish_non_exhaustive()
+    }
+}
+
+struct DirtyCsrs<'a, T: Xlen> {
+    shmem: &'a Shmem<T>,
+}
+
+impl<T: Xlen + core::fmt::Debug> core::fmt::Debug for DirtyCsrs<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map()
+            .entries(
+                self.shmem
+                    .dirty_csrs()
+                    .filter_map(|csr| Some((csr, self.shmem.csr(csr).ok()?))),
+            )
+            .finish()
+    }
+}
+
+/// Nested HFENCE types in the config word of a nested HFENCE entry.
+///
+/// Declared in §15.
+pub mod hfence_type {
+    /// `HFENCE.GVMA` on a range of guest physical pages for all VMIDs.
+    pub const GVMA: u64 = 0x0;
+    /// `HFENCE.GVMA` on all guest physical pages for all VMIDs.
+    pub const GVMA_ALL: u64 = 0x1;
+    /// `HFENCE.GVMA` on a range of guest physical pages for one VMID.
+    pub const GVMA_VMID: u64 = 0x2;
+    /// `HFENCE.GVMA` on all guest physical pages for one VMID.
+    pub const GVMA_VMID_ALL: u64 = 0x3;
+    /// `HFENCE.VVMA` on a range of guest virtual pages for one VMID.
+    pub const VVMA: u64 = 0x4;
+    /// `HFENCE.VVMA` on all guest virtual pages for one VMID.
+    pub const VVMA_ALL: u64 = 0x5;
+    /// `HFENCE.VVMA` on a range of guest virtual pages for one VMID and ASID.
+    pub const VVMA_ASID: u64 = 0x6;
+    /// `HFENCE.VVMA` on all guest virtual pages for one VMID and ASID.
+    pub const VVMA_ASID_ALL: u64 = 0x7;
+}
+
+/// Smallest page order of a nested HFENCE entry, i.e. 4 KiB pages.
+pub const HFENCE_ORDER_BASE: u32 = 12;
+
+/// Nested HFENCE request decoded from, or to be encoded into, a nested HFENCE entry.
+///
+/// Page ranges start at address `page_num << order` and cover `page_count` pages
+/// of `1 << order` bytes each; `order` is at least [`HFENCE_ORDER_BASE`].
+///
+/// Declared in §15.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Hfence {
+    /// `HFENCE.GVMA` on a range of guest physical pages for all VMIDs.
+    Gvma {
+        /// Page order, i.e. log2 of the page size in bytes.
+        order: u32,
+        /// Guest physical page number in units of the page size.
+        page_num: u64,
+        /// Number of pages.
+        page_count: u64,
+    },
+    /// `HFENCE.GVMA` on all guest physical pages for all VMIDs.
+    GvmaAll,
+    /// `HFENCE.GVMA` on a range of guest physical pages for one VMID.
+    GvmaVmid {
+        /// Virtual machine identifier.
+        vmid: u16,
+        /// Page order, i.e. log2 of the page size in bytes.
+        order: u32,
+        /// Guest physical page number in units of the page size.
+        page_num: u64,
+        /// Number of pages.
+        page_count: u64,
+    },
+    /// `HFENCE.GVMA` on all guest physical pages for one VMID.
+    GvmaVmidAll {
+        /// Virtual machine identifier.
+        vmid: u16,
+    },
+    /// `HFENCE.VVMA` on a range of guest virtual pages for one VMID.
+    Vvma {
+        /// Virtual machine identifier.
+        vmid: u16,
+        /// Page order, i.e. log2 of the page size in bytes.
+        order: u32,
+        /// Guest virtual page number in units of the page size.
+        page_num: u64,
+        /// Number of pages.
+        page_count: u64,
+    },
+    /// `HFENCE.VVMA` on all guest virtual pages for one VMID.
+    VvmaAll {
+        /// Virtual machine identifier.
+        vmid: u16,
+    },
+    /// `HFENCE.VVMA` on a range of guest virtual pages for one VMID and ASID.
+    VvmaAsid {
+        /// Virtual machine identifier.
+        vmid: u16,
+        /// Address space identifier.
+        asid: u16,
+        /// Page order, i.e. log2 of the page size in bytes.
+        order: u32,
+        /// Guest virtual page number in units of the page size.
+        page_num: u64,
+        /// Number of pages.
+        page_count: u64,
+    },
+    /// `HFENCE.VVMA` on all guest virtual pages for one VMID and ASID.
+    VvmaAsidAll {
+        /// Virtual machine identifier.
+        vmid: u16,
+        /// Address space identifier.
+        asid: u16,
+    },
+}
+
+/// Nested HFENCE entry in the scratch space of NACL shared memory.
+///
+/// An entry consists of four `XLEN`-wide words: the config word, the page number,
+/// a reserved word and the page count. The config word is laid out as follows,
+/// where VMID and ASID are 7 and 9 bits wide on RV32, or 14 and 16 bits wide on RV64:
+///
+/// | Bits                     | Field                          |
+/// |:-------------------------|:-------------------------------|
+/// | `XLEN-1`                 | Pending                        |
+/// | `XLEN-2 : XLEN-4`        | Reserved                       |
+/// | `XLEN-5 : XLEN-8`        | Type, as in module [`hfence_type`] |
+/// | `XLEN-9`                 | Reserved                       |
+/// | `XLEN-10 : XLEN-16`      | Order minus [`HFENCE_ORDER_BASE`] |
+/// | above ASID, below Order  | VMID, and reserved bits on RV64 |
+/// | lowest bits              | ASID                           |
+///
+/// Declared in §15.
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::nacl::{Hfence, HfenceEntry};
+/// let hfence = Hfence::VvmaAsidAll { vmid: 1, asid: 2 };
+/// let entry = HfenceEntry::<u64>::encode(hfence).unwrap();
+/// assert!(entry.is_pending());
+/// assert_eq!(entry.config(), 0x8700_0000_0001_0002);
+/// assert_eq!(entry.decode(), Ok(hfence));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct HfenceEntry<T> {
+    config: T,
+    page_num: T,
+    reserved: T,
+    page_count: T,
+}
+
+impl<T: Xlen> HfenceEntry<T> {
+    const EMPTY: Self = Self {
+        config: T::ZERO,
+        page_num: T::ZERO,
+        reserved: T::ZERO,
+        page_count: T::ZERO,
+    };
+
+    /// Encodes a nested HFENCE request into a pending entry.
+    ///
+    /// Returns [`Error::InvalidParam`] if the VMID, ASID, page order, page number or
+    /// page count does not fit in its field for this `XLEN`.
+    pub fn encode(hfence: Hfence) -> Result<Self, Error> {
+        let (ty, vmid, asid, order, page_num, page_count) = match hfence {
+            Hfence::Gvma {
+                order,
+                page_num,
+                page_count,
+            } => (hfence_type::GVMA, 0, 0, order, page_num, page_count),
+            Hfence::GvmaAll => (hfence_type::GVMA_ALL, 0, 0, HFENCE_ORDER_BASE, 0, 0),
+            Hfence::GvmaVmid {
+                vmid,
+                order,
+                page_num,
+                page_count,
+            } => (hfence_type::GVMA_VMID, vmid, 0, order, page_num, page_count),
+            Hfence::GvmaVmidAll { vmid } => {
+                (hfence_type::GVMA_VMID_ALL, vmid, 0, HFENCE_ORDER_BASE, 0, 0)
+            }
+            Hfence::Vvma {
+                vmid,
+                order,
+                page_num,
+                page_count,
+            } => (hfence_type::VVMA, vmid, 0, order, page_num, page_count),
+            Hfence::VvmaAll { vmid } => (hfence_type::VVMA_ALL, vmid, 0, HFENCE_ORDER_BASE, 0, 0),
+            Hfence::VvmaAsid {
+                vmid,
+                asid,
+                order,
+                page_num,
+                page_count,
+            } => (
+                hfence_type::VVMA_ASID,
+                vmid,
+                asid,
+                order,
+                page_num,
+                page_count,
+            ),
+            Hfence::VvmaAsidAll { vmid, asid } => (
+                hfence_type::VVMA_ASID_ALL,
+                vmid,
+                asid,
+                HFENCE_ORDER_BASE,
+                0,
+                0,
+            ),
+        };
+        let (vmid_bits, asid_bits) = Self::id_bits();
+        let order = order.wrapping_sub(HFENCE_ORDER_BASE) as u64;
+        if (vmid as u64) >> vmid_bits != 0
+            || (asid as u64) >> asid_bits != 0
+            || order > 0x7f
+            || T::from_u64(page_num).to_u64() != page_num
+            || T::from_u64(page_count).to_u64() != page_count
+        {
+            return Err(Error::InvalidParam);
+        }
+        let config = (1 << (T::BITS - 1))
+            | (ty << (T::BITS - 8))
+            | (order << (T::BITS - 16))
+            | ((vmid as u64) << asid_bits)
+            | asid as u64;
+        Ok(Self {
+            config: T::from_u64(config),
+            page_num: T::from_u64(page_num),
+            reserved: T::ZERO,
+            page_count: T::from_u64(page_count),
+        })
+    }
+
+    /// Decodes the nested HFENCE request of this entry, regardless of its pending bit.
+    ///
+    /// Returns [`Error::InvalidParam`] if the type is reserved or reserved bits are set.
+    pub fn decode(self) -> Result<Hfence, Error> {
+        let config = self.config.to_u64();
+        let (vmid_bits, asid_bits) = Self::id_bits();
+        let id_bits = vmid_bits + asid_bits;
+        let reserved = ((config >> (T::BITS - 4)) & 0b111)
+            | ((config >> (T::BITS - 9)) & 0b1)
+            | ((config >> id_bits) & ((1 << (T::BITS - 16 - id_bits)) - 1));
+        if reserved != 0 {
+            return Err(Error::InvalidParam);
+        }
+        let ty = (config >> (T::BITS - 8)) & 0xf;
+        let order = ((config >> (T::BITS - 16)) & 0x7f) as u32 + HFENCE_ORDER_BASE;
+        let vmid = ((config >> asid_bits) & ((1 << vmid_bits) - 1)) as u16;
+        let asid = (config & ((1 << asid_bits) - 1)) as u16;
+        let page_num = self.page_num.to_u64();
+        let page_count = self.page_count.to_u64();
+        Ok(match ty {
+            hfence_type::GVMA => Hfence::Gvma {
+                order,
+                page_num,
+                page_count,
+            },
+            hfence_type::GVMA_ALL => Hfence::GvmaAll,
+            hfence_type::GVMA_VMID => Hfence::GvmaVmid {
+                vmid,
+                order,
+                page_num,
+                page_count,
+            },
+            hfence_type::GVMA_VMID_ALL => Hfence::GvmaVmidAll { vmid },
+            hfence_type::VVMA => Hfence::Vvma {
+                vmid,
+                order,
+                page_num,
+                page_count,
+            },
+            hfence_type::VVMA_ALL => Hfence::VvmaAll { vmid },
+            hfence_type::VVMA_ASID => Hfence::VvmaAsid {
+                vmid,
+                asid,
+                order,
+                page_num,
+                page_count,
+            },
+            hfence_type::VVMA_ASID_ALL => Hfence::VvmaAsidAll { vmid, asid },
+            _ => return Err(Error::InvalidParam),
+        })
+    }
+
+    /// Returns `true` if this entry is pending to be processed by the SBI implementation.
+    #[inline]
+    pub fn is_pending(self) -> bool {
+        self.config.to_u64() >> (T::BITS - 1) != 0
+    }
+
+    /// Clears the pending bit of this entry.
+    #[inline]
+    pub fn clear_pending(&mut self) {
+        self.config = T::from_u64(self.config.to_u64() & !(1 << (T::BITS - 1)));
+    }
+
+    /// Returns the raw config word.
+    #[inline]
+    pub fn config(self) -> T {
+        self.config
+    }
+
+    /// Returns the raw page number word.
+    #[inline]
+    pub fn page_num(self) -> T {
+        self.page_num
+    }
+
+    /// Returns the raw page count word.
+    #[inline]
+    pub fn page_count(self) -> T {
+        self.page_count
+    }
+
+    /// Returns the widths of VMID and ASID fields for this `XLEN`.
+    #[inline]
+    fn id_bits() -> (u32, u32) {
+        if T::BITS == 32 {
+            (7, 9)
+        } else {
+            (14, 16)
+        }
+    }
+}
+
+impl<T: Xlen> Shmem<T> {
+    /// Returns the nested HFENCE entries in scratch space.
+    #[inline]
+    pub fn hfence_entries(&self) -> &[HfenceEntry<T>] {
+        self.hfence_entries.as_ref()
+    }
+
+    /// Returns the mutable nested HFENCE entries in scratch space.
+    #[inline]
+    pub fn hfence_entries_mut(&mut self) -> &mut [HfenceEntry<T>] {
+        self.hfence_entries.as_mut()
+    }
+
+    /// Consumes pending nested HFENCE entries in the range of a synchronize HFENCE call.
+    ///
+    /// The returned iterator decodes each pending entry in `entry_index..entry_index + entry_count`
+    /// and clears its pending bit; entries that are not pending are skipped.
+    ///
+    /// Returns [`Error::InvalidParam`] if the range is out of the nested HFENCE entries.
+    #[inline]
+    pub fn drain_hfence(
+        &mut self,
+        entry_index: usize,
+        entry_count: usize,
+    ) -> Result<HfenceDrain<'_, T>, Error> {
+        let entries = self.hfence_entries_mut();
+        match entry_index.checked_add(entry_count) {
+            Some(end) if end <= entries.len() => Ok(HfenceDrain {
+                entries: entries[entry_index..end].iter_mut(),
+            }),
+            _ => Err(Error::InvalidParam),
+        }
+    }
+}
+
+/// Iterator consuming pending nested HFENCE entries, created by [`Shmem::drain_hfence`].
+///
+/// Yields the decoded request of each pending entry, or [`Error::InvalidParam`] if the
+/// entry is malformed. The pending bit is cleared on every entry yielded.
+pub struct HfenceDrain<'a, T> {
+    entries: core::slice::IterMut<'a, HfenceEntry<T>>,
+}
+
+impl<T: Xlen> Iterator for HfenceDrain<'_, T> {
+    type Item = Result<Hfence, Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.entries.find(|entry| entry.is_pending())?;
+        entry.clear_pending();
+        Some(entry.decode())
+    }
+}
+
+/// Ring-style cursor for supervisor software to enqueue nested HFENCE entries.
+///
+/// The cursor remembers where the last entry was enqueued, and looks for the next
+/// entry not pending from there, wrapping around at the end of the entries.
+/// Once entries are enqueued, supervisor software calls the synchronize HFENCE
+/// function so that the SBI implementation consumes them.
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::nacl::{Hfence, HfenceCursor, ShmemRv64};
+/// let mut shmem = ShmemRv64::new();
+/// let mut cursor = HfenceCursor::new();
+/// let gvma = Hfence::GvmaVmidAll { vmid: 3 };
+/// assert_eq!(cursor.enqueue(&mut shmem, gvma), Ok(0));
+/// assert_eq!(cursor.enqueue(&mut shmem, Hfence::GvmaAll), Ok(1));
+/// // SBI implementation on synchronize HFENCE call with all entries
+/// let len = shmem.hfence_entries().len();
+/// let mut drain = shmem.drain_hfence(0, len).unwrap();
+/// assert_eq!(drain.next(), Some(Ok(gvma)));
+/// assert_eq!(drain.next(), Some(Ok(Hfence::GvmaAll)));
+/// assert_eq!(drain.next(), None);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HfenceCursor {
+    next: usize,
+}
+
+impl HfenceCursor {
+    /// Creates a cursor starting from the first nested HFENCE entry.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { next: 0 }
+    }
+
+    /// Encodes and enqueues a nested HFENCE request, returning the index of its entry.
+    ///
+    /// Returns [`Error::InvalidParam`] if the request cannot be encoded, or [`Error::Failed`]
+    /// if all entries are pending; supervisor software should then call the synchronize
+    /// HFENCE function before enqueuing again.
+    pub fn enqueue<T: Xlen>(
+        &mut self,
+        shmem: &mut Shmem<T>,
+        hfence: Hfence,
+    ) -> Result<usize, Error> {
+        let entry = HfenceEntry::encode(hfence)?;
+        let entries = shmem.hfence_entries_mut();
+        let len = entries.len();
+        for offset in 0..len {
+            let idx = (self.next + offset) % len;
+            if !entries[idx].is_pending() {
+                entries[idx] = entry;
+                self.next = (idx + 1) % len;
+                return Ok(idx);
+            }
+        }
+        Err(Error::Failed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        csr_index, csr_num, shmem_offset, Hfence, HfenceCursor, HfenceEntry, Shmem, ShmemRv32,
+        ShmemRv64, Xlen, CSR_SLOTS,
+    };
+    use crate::binary::Error;
+    use core::mem::offset_of;
+
+    #[test]
+    fn shmem_layout() {
+        assert_eq!(
+            offset_of!(ShmemRv32, csr_dirty_bitmap),
+            shmem_offset::CSR_DIRTY_BITMAP
+        );
+        assert_eq!(
+            offset_of!(ShmemRv64, csr_dirty_bitmap),
+            shmem_offset::CSR_DIRTY_BITMAP
+        );
+        assert_eq!(offset_of!(ShmemRv32, csrs), shmem_offset::CSR_SPACE);
+        assert_eq!(offset_of!(ShmemRv64, csrs), shmem_offset::CSR_SPACE);
+    }
+
+    #[test]
+    fn csr_index_mapping() {
+        for idx in 0..CSR_SLOTS {
+            let csr = csr_num(idx).unwrap();
+            assert_eq!(csr_index(csr), Some(idx));
+        }
+        assert_eq!(csr_num(CSR_SLOTS), None);
+        // vsstatus, hgatp, and a custom hypervisor CSR at 0xEFF
+        assert_eq!(csr_index(0x200), Some(0x000));
+        assert_eq!(csr_index(0x680), Some(0x180));
+        assert_eq!(csr_index(0xeff), Some(0x3ff));
+        assert_eq!(csr_index(0x300), None);
+        assert_eq!(csr_index(0x1200), None);
+    }
+
+    #[test]
+    fn shmem_csr_dirty() {
+        let mut shmem = ShmemRv32::new();
+        assert_eq!(shmem.dirty_csrs().next(), None);
+        assert_eq!(shmem.set_csr(0x100, 1), Err(Error::InvalidParam));
+        assert_eq!(shmem.csr(0x100), Err(Error::InvalidParam));
+        shmem.set_csr(0x680, 0x8000_0000).unwrap();
+        shmem.set_csr(0x200, 0xffff_ffff).unwrap();
+        assert_eq!(shmem.csr(0x680), Ok(0x8000_0000));
+        assert!(shmem.dirty_csrs().eq([0x200, 0x680]));
+        assert!(shmem.clear_csr_dirty(0x200));
+        assert!(!shmem.clear_csr_dirty(0x200));
+        assert!(!shmem.is_csr_dirty(0x200));
+        assert_eq!(shmem.csr(0x200), Ok(0xffff_ffff));
+        shmem.clear_all_csr_dirty();
+        assert!(!shmem.is_csr_dirty(0x680));
+    }
+
+    fn hfence_round_trip<T: Xlen + core::fmt::Debug + PartialEq>() {
+        let (max_vmid, max_asid) = if T::BITS == 32 {
+            (0x7f, 0x1ff)
+        } else {
+            (0x3fff, 0xffff)
+        };
+        let hfences = [
+            Hfence::Gvma {
+                order: 12,
+                page_num: 0x8_0000,
+                page_count: 1,
+            },
+            Hfence::GvmaAll,
+            Hfence::GvmaVmid {
+                vmid: max_vmid,
+                order: 21,
+                page_num: 0x400,
+                page_count: 2,
+            },
+            Hfence::GvmaVmidAll { vmid: max_vmid },
+            Hfence::Vvma {
+                vmid: 1,
+                order: 30,
+                page_num: 3,
+                page_count: 4,
+            },
+            Hfence::VvmaAll { vmid: 1 },
+            Hfence::VvmaAsid {
+                vmid: max_vmid,
+                asid: max_asid,
+                order: 12 + 0x7f,
+                page_num: 0xffff_ffff,
+                page_count: 0xffff_ffff,
+            },
+            Hfence::VvmaAsidAll {
+                vmid: max_vmid,
+                asid: max_asid,
+            },
+        ];
+        for hfence in hfences {
+            let mut entry = HfenceEntry::<T>::encode(hfence).unwrap();
+            assert!(entry.is_pending());
+            assert_eq!(entry.decode(), Ok(hfence));
+            entry.clear_pending();
+            assert!(!entry.is_pending());
+            assert_eq!(entry.decode(), Ok(hfence));
+        }
+        let invalid = [
+            Hfence::GvmaVmidAll { vmid: max_vmid + 1 },
+            Hfence::Gvma {
+                order: 11,
+                page_num: 0,
+                page_count: 1,
+            },
+            Hfence::Gvma {
+                order: 12 + 0x80,
+                page_num: 0,
+                page_count: 1,
+            },
+        ];
+        for hfence in invalid {
+            assert_eq!(HfenceEntry::<T>::encode(hfence), Err(Error::InvalidParam));
+        }
+    }
+
+    #[test]
+    fn hfence_entry() {
+        hfence_round_trip::<u32>();
+        hfence_round_trip::<u64>();
+        hfence_round_trip::<usize>();
+        let asid = Hfence::VvmaAsidAll {
+            vmid: 0,
+            asid: 0x200,
+        };
+        assert_eq!(HfenceEntry::<u32>::encode(asid), Err(Error::InvalidParam));
+
+        let hfence = Hfence::VvmaAsid {
+            vmid: 0x7f,
+            asid: 0x1ff,
+            order: 13,
+            page_num: 5,
+            page_count: 6,
+        };
+        let entry = HfenceEntry::<u32>::encode(hfence).unwrap();
+        assert_eq!(entry.config(), 0x8601_ffff);
+        assert_eq!((entry.page_num(), entry.page_count()), (5, 6));
+        let wide = Hfence::Gvma {
+            order: 12,
+            page_num: 0x1_0000_0000,
+            page_count: 1,
+        };
+        assert_eq!(HfenceEntry::<u32>::encode(wide), Err(Error::InvalidParam));
+        assert!(HfenceEntry::<u64>::encode(wide).is_ok());
+
+        let mut shmem = ShmemRv64::new();
+        // reserved type, and reserved bits between ASID and order on RV64
+        shmem.hfence_entries_mut()[0].config = 0x8800_0000_0000_0000;
+        shmem.hfence_entries_mut()[1].config = 0x8100_0000_4000_0000;
+        let mut drain = shmem.drain_hfence(0, 2).unwrap();
+        assert_eq!(drain.next(), Some(Err(Error::InvalidParam)));
+        assert_eq!(drain.next(), Some(Err(Error::InvalidParam)));
+        assert_eq!(drain.next(), None);
+        assert!(shmem.hfence_entries().iter().all(|e| !e.is_pending()));
+    }
+
+    fn hfence_ring<T: Xlen>(mut shmem: Shmem<T>, len: usize) {
+        assert_eq!(shmem.hfence_entries().len(), len);
+        let mut cursor = HfenceCursor::new();
+        for i in 0..len {
+            let hfence = Hfence::VvmaAll {
+                vmid: i as u16 % 64,
+            };
+            assert_eq!(cursor.enqueue(&mut shmem, hfence), Ok(i));
+        }
+        assert_eq!(
+            cursor.enqueue(&mut shmem, Hfence::GvmaAll),
+            Err(Error::Failed)
+        );
+        assert_eq!(shmem.drain_hfence(len, 1).err(), Some(Error::InvalidParam));
+        assert_eq!(
+            shmem.drain_hfence(1, usize::MAX).err(),
+            Some(Error::InvalidParam)
+        );
+        // consume entries 2 and 3 only, then the cursor wraps around to reuse them
+        let drained = shmem.drain_hfence(2, 2).unwrap();
+        assert!(drained.eq([
+            Ok(Hfence::VvmaAll { vmid: 2 }),
+            Ok(Hfence::VvmaAll { vmid: 3 })
+        ]));
+        assert_eq!(cursor.enqueue(&mut shmem, Hfence::GvmaAll), Ok(2));
+        assert_eq!(cursor.enqueue(&mut shmem, Hfence::GvmaAll), Ok(3));
+        assert_eq!(
+            cursor.enqueue(&mut shmem, Hfence::GvmaAll),
+            Err(Error::Failed)
+        );
+        assert_eq!(shmem.drain_hfence(0, len).unwrap().count(), len);
+        assert_eq!(shmem.drain_hfence(0, len).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn hfence_cursor() {
+        hfence_ring(ShmemRv32::new(), 120);
+        hfence_ring(ShmemRv64::new(), 60);
+    }
+}
@@ -4,6 +4,8 @@
 pub const EID_PMU: usize = crate::eid_from_str("PMU") as _;
 pub use fid::*;
 
+use crate::binary::Error;
+
 /// Declared in §11.11.
 mod fid {
     /// Function ID to get the number of counters, both hardware and firmware.
@@ -180,8 +182,893 @@ pub mod firmware_event {
     pub const HFENCE_VVMA_ASID_SENT: usize = 20;
     /// Received HFENCE.VVMA with ASID request from other HART event.
     pub const HFENCE_VVMA_ASID_RECEIVED: usize = 21;
+    /// First SBI implementation specific firmware event code.
+    pub const IMPL_START: usize = 256;
+    /// Last SBI implementation specific firmware event code.
+    pub const IMPL_END: usize = 65534;
     /// RISC-V platform specific firmware events.
     ///
     /// The `event_data` configuration (or parameter) contains the event encoding.
     pub const PLATFORM: usize = 65535;
 }
+
+/// Event index of a PMU event.
+///
+/// An event index is a 20-bit number where bits `[19:16]` are the event type, and
+/// bits `[15:0]` are the event code.
+///
+/// Declared in §11.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct EventIdx {
+    raw: usize,
+}
+
+impl EventIdx {
+    const TYPE_SHIFT: usize = 16;
+    const TYPE_MASK: usize = 0xf;
+    const CODE_MASK: usize = 0xffff;
+
+    /// Converts a raw `event_idx` parameter into `EventIdx`.
+    #[inline]
+    pub const fn from_raw(raw: usize) -> Self {
+        Self { raw }
+    }
+
+    /// Returns the raw `event_idx` parameter.
+    #[inline]
+    pub const fn raw(self) -> usize {
+        self.raw
+    }
+
+    /// Creates an event index from event type and event code.
+    ///
+    /// Bits out of the 4-bit event type and the 16-bit event code are ignored.
+    #[inline]
+    pub const fn new(event_type: usize, event_code: usize) -> Self {
+        Self {
+            raw: ((event_type & Self::TYPE_MASK) << Self::TYPE_SHIFT)
+                | (event_code & Self::CODE_MASK),
+        }
+    }
+
+    /// Creates an event index for a hardware general event in module `hardware_event`.
+    #[inline]
+    pub const fn hardware_general(event_code: usize) -> Self {
+        Self::new(event_type::HARDWARE_GENERAL, event_code)
+    }
+
+    /// Creates an event index for a hardware cache event.
+    ///
+    /// Parameters are values in modules `cache_event`, `cache_operation` and `cache_result`.
+    #[inline]
+    pub const fn hardware_cache(cache_id: usize, op_id: usize, result_id: usize) -> Self {
+        Self::new(
+            event_type::HARDWARE_CACHE,
+            (cache_id << 3) | ((op_id & 0b11) << 1) | (result_id & 0b1),
+        )
+    }
+
+    /// Creates an event index for hardware raw events.
+    ///
+    /// The raw event value is passed in the `event_data` parameter.
+    #[inline]
+    pub const fn hardware_raw() -> Self {
+        Self::new(event_type::HARDWARE_RAW, 0)
+    }
+
+    /// Creates an event index for a firmware event in module `firmware_event`.
+    #[inline]
+    pub const fn firmware(event_code: usize) -> Self {
+        Self::new(event_type::FIRMWARE, event_code)
+    }
+
+    /// Reads the event type.
+    #[inline]
+    pub const fn event_type(self) -> usize {
+        (self.raw >> Self::TYPE_SHIFT) & Self::TYPE_MASK
+    }
+
+    /// Reads the event code.
+    #[inline]
+    pub const fn event_code(self) -> usize {
+        self.raw & Self::CODE_MASK
+    }
+
+    /// Decodes this event index along with the `event_data` parameter.
+    ///
+    /// Returns [`Error::InvalidParam`] if the event index has reserved bits set, its
+    /// event type or event code is unknown, or `event_data` is out of range for a
+    /// hardware raw event.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sbi_spec::{binary::{Error, SbiRet}, pmu::{self, Event, EventIdx}};
+    /// let idx = EventIdx::hardware_cache(
+    ///     pmu::cache_event::DTLB,
+    ///     pmu::cache_operation::READ,
+    ///     pmu::cache_result::MISS,
+    /// );
+    /// assert_eq!(idx.raw(), 0x1_0019);
+    /// assert_eq!(
+    ///     idx.decode(0),
+    ///     Ok(Event::HardwareCache {
+    ///         cache_id: pmu::cache_event::DTLB,
+    ///         op_id: pmu::cache_operation::READ,
+    ///         result_id: pmu::cache_result::MISS,
+    ///     })
+    /// );
+    /// // unknown event index are reported as invalid parameter
+    /// let ret = EventIdx::from_raw(0x5_0000).decode(0);
+    /// assert_eq!(ret, Err(Error::InvalidParam));
+    /// assert_eq!(SbiRet::from(ret.unwrap_err()), SbiRet::invalid_param());
+    /// ```
+    pub const fn decode(self, event_data: u64) -> Result<Event, Error> {
+        if (self.raw >> Self::TYPE_SHIFT) & !Self::TYPE_MASK != 0 {
+            return Err(Error::InvalidParam);
+        }
+        let code = self.event_code();
+        match self.event_type() {
+            event_type::HARDWARE_GENERAL => match code {
+                hardware_event::CPU_CYCLES..=hardware_event::REF_CPU_CYCLES => {
+                    Ok(Event::HardwareGeneral(code))
+                }
+                _ => Err(Error::InvalidParam),
+            },
+            event_type::HARDWARE_CACHE => {
+                let (cache_id, op_id, result_id) = (code >> 3, (code >> 1) & 0b11, code & 0b1);
+                if cache_id > cache_event::NODE || op_id > cache_operation::PREFETCH {
+                    return Err(Error::InvalidParam);
+                }
+                Ok(Event::HardwareCache {
+                    cache_id,
+                    op_id,
+                    result_id,
+                })
+            }
+            event_type::HARDWARE_RAW => {
+                if code != 0 || event_data >> RAW_EVENT_BITS != 0 {
+                    return Err(Error::InvalidParam);
+                }
+                Ok(Event::HardwareRaw(event_data))
+            }
+            event_type::FIRMWARE => match code {
+                firmware_event::MISALIGNED_LOAD..=firmware_event::HFENCE_VVMA_ASID_RECEIVED => {
+                    Ok(Event::Firmware(code))
+                }
+                firmware_event::IMPL_START..=firmware_event::IMPL_END => {
+                    Ok(Event::FirmwareImpl(code))
+                }
+                firmware_event::PLATFORM => Ok(Event::FirmwarePlatform(event_data)),
+                _ => Err(Error::InvalidParam),
+            },
+            _ => Err(Error::InvalidParam),
+        }
+    }
+}
+
+/// Number of bits in the raw event value of hardware raw events.
+///
+/// Declared in §11.3.
+pub const RAW_EVENT_BITS: u32 = 48;
+
+/// PMU event decoded from the event index and event data.
+///
+/// Declared in §11.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// Hardware general event with event code in module `hardware_event`.
+    HardwareGeneral(usize),
+    /// Hardware cache event.
+    HardwareCache {
+        /// Cache event ID in module `cache_event`.
+        cache_id: usize,
+        /// Cache operation ID in module `cache_operation`.
+        op_id: usize,
+        /// Cache operation result ID in module `cache_result`.
+        result_id: usize,
+    },
+    /// Hardware raw event with the 48-bit raw event value from `event_data`.
+    HardwareRaw(u64),
+    /// Firmware event with event code in module `firmware_event`.
+    Firmware(usize),
+    /// SBI implementation specific firmware event with event code from
+    /// `firmware_event::IMPL_START` to `firmware_event::IMPL_END`.
+    FirmwareImpl(usize),
+    /// Platform specific firmware event with event encoding from `event_data`.
+    FirmwarePlatform(u64),
+}
+
+impl Event {
+    /// Returns the event index of this event.
+    #[inline]
+    pub const fn event_idx(self) -> EventIdx {
+        match self {
+            Self::HardwareGeneral(code) => EventIdx::hardware_general(code),
+            Self::HardwareCache {
+                cache_id,
+                op_id,
+                result_id,
+            } => EventIdx::hardware_cache(cache_id, op_id, result_id),
+            Self::HardwareRaw(_) => EventIdx::hardware_raw(),
+            Self::Firmware(code) | Self::FirmwareImpl(code) => EventIdx::firmware(code),
+            Self::FirmwarePlatform(_) => EventIdx::firmware(firmware_event::PLATFORM),
+        }
+    }
+
+    /// Returns the `event_data` parameter of this event, or zero if not used.
+    #[inline]
+    pub const fn event_data(self) -> u64 {
+        match self {
+            Self::HardwareRaw(data) | Self::FirmwarePlatform(data) => data,
+            _ => 0,
+        }
+    }
+}
+
+/// Counter information returned by the counter get info function.
+///
+/// Bits `[11:0]` are the CSR number, bits `[17:12]` are one less than the width of the
+/// counter, and bit `XLEN-1` is set for firmware counters. For firmware counters,
+/// the CSR number and width should be ignored.
+///
+/// Declared in §11.6.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct CounterInfo {
+    raw: usize,
+}
+
+impl CounterInfo {
+    const CSR_MASK: usize = 0xfff;
+    const WIDTH_SHIFT: usize = 12;
+    const WIDTH_MASK: usize = 0x3f;
+    const TYPE_FIRMWARE: usize = 1 << (usize::BITS - 1);
+
+    /// Converts a raw counter information value into `CounterInfo`.
+    #[inline]
+    pub const fn from_raw(raw: usize) -> Self {
+        Self { raw }
+    }
+
+    /// Returns the raw counter information value.
+    #[inline]
+    pub const fn raw(self) -> usize {
+        self.raw
+    }
+
+    /// Creates counter information for a hardware counter.
+    ///
+    /// `csr` is the 12-bit CSR number of the counter, and `width` is the number of bits
+    /// of the counter CSR, from 1 to 64. Returns `None` if either is out of range.
+    #[inline]
+    pub const fn hardware(csr: u16, width: u32) -> Option<Self> {
+        if csr as usize > Self::CSR_MASK || width == 0 || width > 64 {
+            return None;
+        }
+        Some(Self {
+            raw: csr as usize | ((width as usize - 1) << Self::WIDTH_SHIFT),
+        })
+    }
+
+    /// Creates counter information for a firmware counter.
+    #[inline]
+    pub const fn firmware() -> Self {
+        Self {
+            raw: Self::TYPE_FIRMWARE,
+        }
+    }
+
+    /// Returns `true` if this is a firmware counter.
+    #[inline]
+    pub const fn is_firmware(self) -> bool {
+        self.raw & Self::TYPE_FIRMWARE != 0
+    }
+
+    /// Returns `true` if this is a hardware counter.
+    #[inline]
+    pub const fn is_hardware(self) -> bool {
+        !self.is_firmware()
+    }
+
+    /// Reads the CSR number of a hardware counter.
+    #[inline]
+    pub const fn csr(self) -> u16 {
+        (self.raw & Self::CSR_MASK) as u16
+    }
+
+    /// Reads the number of bits of a hardware counter.
+    #[inline]
+    pub const fn width(self) -> u32 {
+        (((self.raw >> Self::WIDTH_SHIFT) & Self::WIDTH_MASK) + 1) as u32
+    }
+}
+
+/// Layout of the PMU snapshot shared memory.
+///
+/// The snapshot shared memory is a 4 KiB region aligned to 4 KiB, written by the SBI
+/// implementation and read by supervisor software.
+///
+/// Counters in the snapshot are relative to the `counter_idx_base` of the counter start
+/// or stop call that takes the snapshot: bit `i` of the overflow bitmap and element `i`
+/// of the counter values belong to counter `counter_idx_base + i`. Accessors here take
+/// that base together with an absolute counter index.
+///
+/// Declared in §11.12.
+#[derive(Clone, PartialEq, Eq)]
+#[repr(C, align(4096))]
+pub struct SnapshotShmem {
+    /// Overflow bitmap of counters, where bit `i` is the overflow status of
+    /// counter `counter_idx_base + i`.
+    pub counter_overflow_bitmap: u64,
+    /// Values of counters, where element `i` is the value of counter `counter_idx_base + i`.
+    pub counter_values: [u64; SNAPSHOT_COUNTERS],
+    reserved: [u8; SNAPSHOT_RESERVED_SIZE],
+}
+
+/// Number of counters in the PMU snapshot shared memory.
+pub const SNAPSHOT_COUNTERS: usize = 64;
+
+const SNAPSHOT_RESERVED_SIZE: usize = 4096 - 8 - 8 * SNAPSHOT_COUNTERS;
+
+impl SnapshotShmem {
+    /// Creates a zeroed snapshot.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            counter_overflow_bitmap: 0,
+            counter_values: [0; SNAPSHOT_COUNTERS],
+            reserved: [0; SNAPSHOT_RESERVED_SIZE],
+        }
+    }
+
+    /// Returns `true` if counter `counter_idx` has overflowed in a snapshot taken
+    /// with `counter_idx_base`.
+    ///
+    /// Returns [`Error::InvalidParam`] if `counter_idx` is outside the snapshot window
+    /// from `counter_idx_base` to `counter_idx_base + 63`.
+    #[inline]
+    pub const fn is_overflow(
+        &self,
+        counter_idx_base: usize,
+        counter_idx: usize,
+    ) -> Result<bool, Error> {
+        match Self::offset(counter_idx_base, counter_idx) {
+            Ok(offset) => Ok(self.counter_overflow_bitmap & (1 << offset) != 0),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Sets or clears the overflow bit of counter `counter_idx` in a snapshot taken
+    /// with `counter_idx_base`.
+    ///
+    /// Returns [`Error::InvalidParam`] if `counter_idx` is outside the snapshot window.
+    #[inline]
+    pub fn set_overflow(
+        &mut self,
+        counter_idx_base: usize,
+        counter_idx: usize,
+        overflow: bool,
+    ) -> Result<(), Error> {
+        let offset = Self::offset(counter_idx_base, counter_idx)?;
+        if overflow {
+            self.counter_overflow_bitmap |= 1 << offset;
+        } else {
+            self.counter_overflow_bitmap &= !(1 << offset);
+        }
+        Ok(())
+    }
+
+    /// Reads the value of counter `counter_idx` in a snapshot taken with `counter_idx_base`.
+    ///
+    /// Returns [`Error::InvalidParam`] if `counter_idx` is outside the snapshot window.
+    #[inline]
+    pub const fn counter_value(
+        &self,
+        counter_idx_base: usize,
+        counter_idx: usize,
+    ) -> Result<u64, Error> {
+        match Self::offset(counter_idx_base, counter_idx) {
+            Ok(offset) => Ok(self.counter_values[offset]),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Writes the value of counter `counter_idx` in a snapshot taken with `counter_idx_base`.
+    ///
+    /// Returns [`Error::InvalidParam`] if `counter_idx` is outside the snapshot window.
+    #[inline]
+    pub fn set_counter_value(
+        &mut self,
+        counter_idx_base: usize,
+        counter_idx: usize,
+        value: u64,
+    ) -> Result<(), Error> {
+        let offset = Self::offset(counter_idx_base, counter_idx)?;
+        self.counter_values[offset] = value;
+        Ok(())
+    }
+
+    #[inline]
+    const fn offset(counter_idx_base: usize, counter_idx: usize) -> Result<usize, Error> {
+        match counter_idx.checked_sub(counter_idx_base) {
+            Some(offset) if offset < SNAPSHOT_COUNTERS => Ok(offset),
+            _ => Err(Error::InvalidParam),
+        }
+    }
+}
+
+impl Default for SnapshotShmem {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for SnapshotShmem {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SnapshotShmem")
+            .field("counter_overflow_bitmap", &self.counter_overflow_bitmap)
+            .field("counter_values", &self.counter_values)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Set of counters selected by `counter_idx_base` and `counter_idx_mask` parameters.
+///
+/// Bit `i` of `counter_idx_mask` selects counter `counter_idx_base + i`. Unlike
+/// [`HartMask`](crate::binary::HartMask), there is no special base value to select
+/// all counters.
+///
+/// Declared in §11.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CounterMask {
+    counter_idx_mask: usize,
+    counter_idx_base: usize,
+}
+
+impl CounterMask {
+    /// Construct a counter mask from mask value and base counter index.
+    #[inline]
+    pub const fn from_mask_base(counter_idx_mask: usize, counter_idx_base: usize) -> Self {
+        Self {
+            counter_idx_mask,
+            counter_idx_base,
+        }
+    }
+
+    /// Returns `counter_idx_mask` and `counter_idx_base` parameters from the counter mask.
+    #[inline]
+    pub const fn into_inner(self) -> (usize, usize) {
+        (self.counter_idx_mask, self.counter_idx_base)
+    }
+
+    /// Check if the counter `counter_idx` is included in this counter mask.
+    #[inline]
+    pub const fn has_bit(&self, counter_idx: usize) -> bool {
+        let Some(idx) = counter_idx.checked_sub(self.counter_idx_base) else {
+            return false;
+        };
+        if idx >= usize::BITS as usize {
+            return false;
+        }
+        self.counter_idx_mask & (1 << idx) != 0
+    }
+
+    /// Returns an iterator over selected counter indices in ascending order.
+    #[inline]
+    pub const fn iter(&self) -> CounterMaskIter {
+        CounterMaskIter {
+            mask: self.counter_idx_mask,
+            base: self.counter_idx_base,
+        }
+    }
+
+    /// Checks that every selected counter is less than `num_counters`.
+    ///
+    /// `num_counters` is the value returned by the number of counters function.
+    /// Returns [`Error::InvalidParam`] if any selected counter is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sbi_spec::{binary::Error, pmu::CounterMask};
+    /// let mask = CounterMask::from_mask_base(0b101, 3);
+    /// assert_eq!(mask.check(6), Ok(mask));
+    /// assert_eq!(mask.check(5), Err(Error::InvalidParam));
+    /// ```
+    #[inline]
+    pub const fn check(self, num_counters: usize) -> Result<Self, Error> {
+        if self.counter_idx_mask == 0 {
+            return Ok(self);
+        }
+        let highest = usize::BITS - 1 - self.counter_idx_mask.leading_zeros();
+        match self.counter_idx_base.checked_add(highest as usize) {
+            Some(idx) if idx < num_counters => Ok(self),
+            _ => Err(Error::InvalidParam),
+        }
+    }
+}
+
+impl IntoIterator for CounterMask {
+    type Item = usize;
+    type IntoIter = CounterMaskIter;
+
+    #[inline]
+    fn into_iter(self) -> CounterMaskIter {
+        self.iter()
+    }
+}
+
+/// Iterator over counter indices selected by a [`CounterMask`].
+#[derive(Clone, Debug)]
+pub struct CounterMaskIter {
+    mask: usize,
+    base: usize,
+}
+
+impl Iterator for CounterMaskIter {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        if self.mask == 0 {
+            return None;
+        }
+        let idx = self.mask.trailing_zeros() as usize;
+        self.mask &= self.mask - 1;
+        match self.base.checked_add(idx) {
+            Some(counter_idx) => Some(counter_idx),
+            None => {
+                // remaining counter indices overflow `usize`
+                self.mask = 0;
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.mask.count_ones() as usize))
+    }
+}
+
+/// Flags for the counter configuration matching function.
+///
+/// Declared in §11.7.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ConfigFlags {
+    raw: usize,
+}
+
+impl ConfigFlags {
+    /// Skip the counter matching.
+    pub const SKIP_MATCH: Self = Self::from_raw(1 << 0);
+    /// Clear (or zero) the counter value in counter configuration.
+    pub const CLEAR_VALUE: Self = Self::from_raw(1 << 1);
+    /// Start the counter after configuring a matching counter.
+    pub const AUTO_START: Self = Self::from_raw(1 << 2);
+    /// Event counting inhibited in VU-mode.
+    pub const SET_VUINH: Self = Self::from_raw(1 << 3);
+    /// Event counting inhibited in VS-mode.
+    pub const SET_VSINH: Self = Self::from_raw(1 << 4);
+    /// Event counting inhibited in U-mode.
+    pub const SET_UINH: Self = Self::from_raw(1 << 5);
+    /// Event counting inhibited in S-mode.
+    pub const SET_SINH: Self = Self::from_raw(1 << 6);
+    /// Event counting inhibited in M-mode.
+    pub const SET_MINH: Self = Self::from_raw(1 << 7);
+
+    const VALID: usize = 0xff;
+}
+
+/// Flags for the counter start function.
+///
+/// Declared in §11.8.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct StartFlags {
+    raw: usize,
+}
+
+impl StartFlags {
+    /// Set the value of counters based on the `initial_value` parameter.
+    pub const INIT_VALUE: Self = Self::from_raw(1 << 0);
+    /// Initialize the given counters from shared memory if available.
+    pub const INIT_SNAPSHOT: Self = Self::from_raw(1 << 1);
+
+    const VALID: usize = 0b11;
+}
+
+/// Flags for the counter stop function.
+///
+/// Declared in §11.9.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct StopFlags {
+    raw: usize,
+}
+
+impl StopFlags {
+    /// Reset the counter to event mapping.
+    pub const RESET: Self = Self::from_raw(1 << 0);
+    /// Save a snapshot of the given counters' values in shared memory if available.
+    pub const TAKE_SNAPSHOT: Self = Self::from_raw(1 << 1);
+
+    const VALID: usize = 0b11;
+}
+
+macro_rules! impl_flags {
+    ($($ty: ident),+) => {
+        $(
+            impl $ty {
+                /// Returns a flag set with no flags.
+                #[inline]
+                pub const fn empty() -> Self {
+                    Self { raw: 0 }
+                }
+
+                /// Converts a raw `flags` parameter into flag set, keeping reserved bits.
+                #[inline]
+                pub const fn from_raw(raw: usize) -> Self {
+                    Self { raw }
+                }
+
+                /// Converts a raw `flags` parameter into flag set.
+                ///
+                /// Returns [`Error::InvalidParam`] if any reserved bit is set.
+                #[inline]
+                pub const fn try_from_raw(raw: usize) -> Result<Self, Error> {
+                    if raw & !Self::VALID != 0 {
+                        return Err(Error::InvalidParam);
+                    }
+                    Ok(Self { raw })
+                }
+
+                /// Returns the raw `flags` parameter.
+                #[inline]
+                pub const fn raw(self) -> usize {
+                    self.raw
+                }
+
+                /// Returns `true` if no flag is set.
+                #[inline]
+                pub const fn is_empty(self) -> bool {
+                    self.raw == 0
+                }
+
+                /// Returns `true` if all flags in `other` are set in `self`.
+                #[inline]
+                pub const fn contains(self, other: Self) -> bool {
+                    self.raw & other.raw == other.raw
+                }
+
+                /// Returns the union of flags in `self` and `other`.
+                #[inline]
+                pub const fn union(self, other: Self) -> Self {
+                    Self {
+                        raw: self.raw | other.raw,
+                    }
+                }
+
+                /// Returns `true` if any reserved bit is set.
+                #[inline]
+                pub const fn has_reserved_bits(self) -> bool {
+                    self.raw & !Self::VALID != 0
+                }
+            }
+
+            impl core::ops::BitOr for $ty {
+                type Output = Self;
+
+                #[inline]
+                fn bitor(self, rhs: Self) -> Self {
+                    self.union(rhs)
+                }
+            }
+
+            impl core::ops::BitOrAssign for $ty {
+                #[inline]
+                fn bitor_assign(&mut self, rhs: Self) {
+                    *self = self.union(rhs);
+                }
+            }
+        )+
+    };
+}
+
+impl_flags!(ConfigFlags, StartFlags, StopFlags);
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        cache_event, cache_operation, cache_result, firmware_event, hardware_event, ConfigFlags,
+        CounterInfo, CounterMask, Event, EventIdx, SnapshotShmem, StartFlags, StopFlags,
+    };
+    use crate::binary::Error;
+
+    #[test]
+    fn counter_flags() {
+        let flags = ConfigFlags::CLEAR_VALUE | ConfigFlags::AUTO_START | ConfigFlags::SET_MINH;
+        assert_eq!(flags.raw(), 0b1000_0110);
+        assert!(flags.contains(ConfigFlags::AUTO_START | ConfigFlags::SET_MINH));
+        assert!(!flags.contains(ConfigFlags::SKIP_MATCH));
+        assert_eq!(
+            ConfigFlags::try_from_raw(0xff),
+            Ok(ConfigFlags::from_raw(0xff))
+        );
+        assert_eq!(ConfigFlags::try_from_raw(0x100), Err(Error::InvalidParam));
+        assert!(ConfigFlags::from_raw(0x100).has_reserved_bits());
+
+        let mut flags = StartFlags::empty();
+        assert!(flags.is_empty());
+        flags |= StartFlags::INIT_SNAPSHOT;
+        assert_eq!(StartFlags::try_from_raw(0b10), Ok(flags));
+        assert_eq!(StartFlags::try_from_raw(0b100), Err(Error::InvalidParam));
+
+        let flags = StopFlags::RESET | StopFlags::TAKE_SNAPSHOT;
+        assert_eq!(StopFlags::try_from_raw(0b11), Ok(flags));
+        assert_eq!(
+            StopFlags::try_from_raw(usize::MAX),
+            Err(Error::InvalidParam)
+        );
+    }
+
+    #[test]
+    fn event_idx() {
+        let idx = EventIdx::hardware_general(hardware_event::CPU_CYCLES);
+        assert_eq!(idx.raw(), 0x0_0001);
+        assert_eq!(idx.decode(0), Ok(Event::HardwareGeneral(1)));
+        assert_eq!(
+            EventIdx::hardware_general(hardware_event::NO_EVENT).decode(0),
+            Err(Error::InvalidParam)
+        );
+        assert_eq!(
+            EventIdx::from_raw(0x0_000b).decode(0),
+            Err(Error::InvalidParam)
+        );
+
+        let idx = EventIdx::hardware_cache(
+            cache_event::NODE,
+            cache_operation::PREFETCH,
+            cache_result::ACCESS,
+        );
+        assert_eq!((idx.event_type(), idx.event_code()), (1, 0x34));
+        assert_eq!(idx.decode(0).map(Event::event_idx), Ok(idx));
+        // reserved cache operation and cache ID
+        assert_eq!(
+            EventIdx::from_raw(0x1_0006).decode(0),
+            Err(Error::InvalidParam)
+        );
+        assert_eq!(
+            EventIdx::from_raw(0x1_0038).decode(0),
+            Err(Error::InvalidParam)
+        );
+
+        let idx = EventIdx::hardware_raw();
+        assert_eq!(idx.raw(), 0x2_0000);
+        let event = idx.decode(0xffff_ffff_ffff).unwrap();
+        assert_eq!(event, Event::HardwareRaw(0xffff_ffff_ffff));
+        assert_eq!(event.event_data(), 0xffff_ffff_ffff);
+        assert_eq!(idx.decode(1 << 48), Err(Error::InvalidParam));
+        assert_eq!(
+            EventIdx::from_raw(0x2_0001).decode(0),
+            Err(Error::InvalidParam)
+        );
+
+        let idx = EventIdx::firmware(firmware_event::SET_TIMER);
+        assert_eq!(idx.raw(), 0xf_0005);
+        assert_eq!(idx.decode(0x55), Ok(Event::Firmware(5)));
+        let idx = EventIdx::firmware(firmware_event::PLATFORM);
+        assert_eq!(idx.decode(0x55), Ok(Event::FirmwarePlatform(0x55)));
+        // boundaries of standard, reserved, SBI implementation and platform event codes
+        let firmware = |code| EventIdx::firmware(code).decode(0);
+        assert_eq!(firmware(21), Ok(Event::Firmware(21)));
+        assert_eq!(firmware(22), Err(Error::InvalidParam));
+        assert_eq!(firmware(255), Err(Error::InvalidParam));
+        assert_eq!(firmware(256), Ok(Event::FirmwareImpl(256)));
+        assert_eq!(firmware(65534), Ok(Event::FirmwareImpl(65534)));
+        assert_eq!(firmware(65535), Ok(Event::FirmwarePlatform(0)));
+        assert_eq!(Event::FirmwareImpl(300).event_idx().raw(), 0xf_012c);
+
+        // unknown event type, and bits above the event index
+        assert_eq!(EventIdx::new(3, 0).decode(0), Err(Error::InvalidParam));
+        assert_eq!(
+            EventIdx::from_raw(0x10_0001).decode(0),
+            Err(Error::InvalidParam)
+        );
+    }
+
+    #[test]
+    fn counter_info() {
+        // cycle counter
+        let info = CounterInfo::hardware(0xc00, 64).unwrap();
+        assert_eq!(info.raw(), 0xc00 | (63 << 12));
+        assert!(info.is_hardware());
+        assert_eq!((info.csr(), info.width()), (0xc00, 64));
+        let info = CounterInfo::from_raw(0xc03 | (47 << 12));
+        assert_eq!(CounterInfo::hardware(0xc03, 48), Some(info));
+        assert_eq!(CounterInfo::hardware(0xc03, 0), None);
+        assert_eq!(CounterInfo::hardware(0xc03, 65), None);
+        assert_eq!(CounterInfo::hardware(0x1000, 64), None);
+        assert_eq!(
+            CounterInfo::hardware(0xfff, 1).map(CounterInfo::width),
+            Some(1)
+        );
+        assert_eq!((info.csr(), info.width()), (0xc03, 48));
+        let info = CounterInfo::firmware();
+        assert!(info.is_firmware());
+        assert_eq!(info.raw(), 1 << (usize::BITS - 1));
+        assert_eq!(info.raw().leading_zeros(), 0);
+    }
+
+    #[test]
+    fn snapshot_shmem() {
+        let mut snapshot = SnapshotShmem::new();
+        snapshot.set_overflow(0, 3, true).unwrap();
+        snapshot.set_overflow(0, 63, true).unwrap();
+        snapshot.set_counter_value(0, 3, 0x1234_5678_9abc).unwrap();
+        assert_eq!(snapshot.counter_overflow_bitmap, (1 << 63) | (1 << 3));
+        assert_eq!(snapshot.is_overflow(0, 3), Ok(true));
+        assert_eq!(snapshot.is_overflow(0, 63), Ok(true));
+        assert_eq!(snapshot.is_overflow(0, 4), Ok(false));
+        assert_eq!(snapshot.is_overflow(0, 64), Err(Error::InvalidParam));
+        assert_eq!(snapshot.counter_value(0, 3), Ok(0x1234_5678_9abc));
+        snapshot.set_overflow(0, 3, false).unwrap();
+        assert_eq!(snapshot.is_overflow(0, 3), Ok(false));
+        // snapshot taken with a non-zero counter index base
+        let mut snapshot = SnapshotShmem::new();
+        snapshot.set_overflow(100, 100, true).unwrap();
+        snapshot.set_overflow(100, 163, true).unwrap();
+        snapshot.set_counter_value(100, 101, 42).unwrap();
+        assert_eq!(snapshot.counter_overflow_bitmap, (1 << 63) | 1);
+        assert_eq!(snapshot.counter_values[1], 42);
+        assert_eq!(snapshot.counter_value(100, 101), Ok(42));
+        assert_eq!(snapshot.is_overflow(100, 163), Ok(true));
+        assert_eq!(snapshot.is_overflow(100, 99), Err(Error::InvalidParam));
+        assert_eq!(snapshot.is_overflow(100, 164), Err(Error::InvalidParam));
+        assert_eq!(
+            snapshot.set_counter_value(100, 0, 1),
+            Err(Error::InvalidParam)
+        );
+        assert_eq!(
+            snapshot.counter_value(usize::MAX, 3),
+            Err(Error::InvalidParam)
+        );
+        assert_ne!(snapshot, SnapshotShmem::default());
+    }
+
+    #[test]
+    fn counter_mask() {
+        let mask = CounterMask::from_mask_base(0b1011, 3);
+        assert_eq!(mask.into_inner(), (0b1011, 3));
+        assert!(!mask.has_bit(2));
+        assert!(mask.has_bit(3) && mask.has_bit(4) && mask.has_bit(6));
+        assert!(!mask.has_bit(5) && !mask.has_bit(7));
+        let mut iter = mask.into_iter();
+        assert_eq!(iter.size_hint(), (0, Some(3)));
+        assert_eq!(
+            (iter.next(), iter.next(), iter.next()),
+            (Some(3), Some(4), Some(6))
+        );
+        assert_eq!(iter.next(), None);
+        // `usize::MAX` is an ordinary base value for counters
+        let mask = CounterMask::from_mask_base(0b11, usize::MAX);
+        assert!(mask.has_bit(usize::MAX));
+        assert!(!mask.has_bit(0));
+        assert!(mask.iter().eq([usize::MAX]));
+        assert_eq!(mask.check(usize::MAX), Err(Error::InvalidParam));
+        let mask = CounterMask::from_mask_base(1 << (usize::BITS - 1), 0);
+        assert!(mask.iter().eq([usize::BITS as usize - 1]));
+        assert_eq!(mask.check(usize::BITS as usize), Ok(mask));
+        assert_eq!(
+            mask.check(usize::BITS as usize - 1),
+            Err(Error::InvalidParam)
+        );
+        let mask = CounterMask::from_mask_base(0, 100);
+        assert_eq!(mask.check(0), Ok(mask));
+        assert_eq!(mask.iter().next(), None);
+    }
+}
@@ -4,6 +4,9 @@
 pub const EID_SRST: usize = crate::eid_from_str("SRST") as _;
 pub use fid::*;
 
+use crate::binary::Error;
+use core::fmt;
+
 /// Shutdown as reset type.
 pub const RESET_TYPE_SHUTDOWN: u32 = 0;
 /// Cold Reboot as reset type.
@@ -23,3 +26,271 @@ mod fid {
     /// Declared in §10.1.
     pub const SYSTEM_RESET: usize = 0;
 }
+
+/// Reset type of the system reset function.
+///
+/// | Value                       | Description                 |
+/// |:----------------------------|:----------------------------|
+/// | `0x0000_0000`               | Shutdown                    |
+/// | `0x0000_0001`               | Cold reboot                 |
+/// | `0x0000_0002`               | Warm reboot                 |
+/// | `0x0000_0003..=0xEFFF_FFFF` | Reserved for future use     |
+/// | `0xF000_0000..=0xFFFF_FFFF` | Vendor or platform specific |
+///
+/// Declared in §10.1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResetType {
+    /// Shutdown.
+    Shutdown,
+    /// Cold reboot.
+    ColdReboot,
+    /// Warm reboot.
+    WarmReboot,
+    /// Reserved reset type for future use.
+    Reserved(u32),
+    /// Vendor or platform specific reset type.
+    Vendor(u32),
+}
+
+impl ResetType {
+    const VENDOR_START: u32 = 0xF000_0000;
+
+    /// Classifies a raw reset type.
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        match raw {
+            RESET_TYPE_SHUTDOWN => Self::Shutdown,
+            RESET_TYPE_COLD_REBOOT => Self::ColdReboot,
+            RESET_TYPE_WARM_REBOOT => Self::WarmReboot,
+            Self::VENDOR_START.. => Self::Vendor(raw),
+            _ => Self::Reserved(raw),
+        }
+    }
+
+    /// Returns the raw reset type.
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        match self {
+            Self::Shutdown => RESET_TYPE_SHUTDOWN,
+            Self::ColdReboot => RESET_TYPE_COLD_REBOOT,
+            Self::WarmReboot => RESET_TYPE_WARM_REBOOT,
+            Self::Reserved(raw) | Self::Vendor(raw) => raw,
+        }
+    }
+
+    /// Checks this reset type with its support on the current platform.
+    ///
+    /// `support` is only called for reset types that are not reserved, so vendor specific
+    /// reset types can be forwarded to a platform hook. Returns [`Error::InvalidParam`]
+    /// if the reset type is reserved or is vendor specific and unimplemented, and
+    /// [`Error::NotSupported`] if the reset type is implemented but misses dependencies,
+    /// or if a standard reset type is not implemented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sbi_spec::{binary::Error, srst::{ResetSupport, ResetType}};
+    /// let support = |ty| match ty {
+    ///     ResetType::Shutdown | ResetType::ColdReboot => ResetSupport::Supported,
+    ///     _ => ResetSupport::Unimplemented,
+    /// };
+    /// assert_eq!(ResetType::Shutdown.check(support), Ok(ResetType::Shutdown));
+    /// assert_eq!(ResetType::WarmReboot.check(support), Err(Error::NotSupported));
+    /// assert_eq!(ResetType::Vendor(0xf000_0000).check(support), Err(Error::InvalidParam));
+    /// ```
+    pub fn check(self, support: impl FnOnce(Self) -> ResetSupport) -> Result<Self, Error> {
+        if let Self::Reserved(_) = self {
+            return Err(Error::InvalidParam);
+        }
+        match (support(self), self) {
+            (ResetSupport::Supported, _) => Ok(self),
+            (ResetSupport::Unimplemented, Self::Vendor(_)) => Err(Error::InvalidParam),
+            _ => Err(Error::NotSupported),
+        }
+    }
+}
+
+impl fmt::Display for ResetType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Shutdown => write!(f, "Shutdown"),
+            Self::ColdReboot => write!(f, "Cold reboot"),
+            Self::WarmReboot => write!(f, "Warm reboot"),
+            Self::Reserved(raw) => write!(f, "Reserved ({raw:#010x})"),
+            Self::Vendor(raw) => write!(f, "Vendor specific ({raw:#010x})"),
+        }
+    }
+}
+
+/// Reset reason of the system reset function.
+///
+/// | Value                       | Description                    |
+/// |:----------------------------|:-------------------------------|
+/// | `0x0000_0000`               | No reason                      |
+/// | `0x0000_0001`               | System failure                 |
+/// | `0x0000_0002..=0xDFFF_FFFF` | Reserved for future use        |
+/// | `0xE000_0000..=0xEFFF_FFFF` | SBI implementation specific    |
+/// | `0xF000_0000..=0xFFFF_FFFF` | Vendor or platform specific    |
+///
+/// Declared in §10.1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResetReason {
+    /// No reason.
+    NoReason,
+    /// System failure.
+    SystemFailure,
+    /// Reserved reset reason for future use.
+    Reserved(u32),
+    /// SBI implementation specific reset reason.
+    SbiImpl(u32),
+    /// Vendor or platform specific reset reason.
+    Vendor(u32),
+}
+
+impl ResetReason {
+    const SBI_IMPL_START: u32 = 0xE000_0000;
+    const VENDOR_START: u32 = 0xF000_0000;
+
+    /// Classifies a raw reset reason.
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        match raw {
+            RESET_REASON_NO_REASON => Self::NoReason,
+            RESET_REASON_SYSTEM_FAILURE => Self::SystemFailure,
+            Self::VENDOR_START.. => Self::Vendor(raw),
+            Self::SBI_IMPL_START.. => Self::SbiImpl(raw),
+            _ => Self::Reserved(raw),
+        }
+    }
+
+    /// Returns the raw reset reason.
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        match self {
+            Self::NoReason => RESET_REASON_NO_REASON,
+            Self::SystemFailure => RESET_REASON_SYSTEM_FAILURE,
+            Self::Reserved(raw) | Self::SbiImpl(raw) | Self::Vendor(raw) => raw,
+        }
+    }
+
+    /// Checks this reset reason with its support on the current platform.
+    ///
+    /// `support` is only called for reset reasons that are not reserved, so SBI
+    /// implementation and vendor specific reasons can be forwarded to a platform hook.
+    /// Returns [`Error::InvalidParam`] if the reset reason is reserved, or is SBI
+    /// implementation or vendor specific and unimplemented, and [`Error::NotSupported`]
+    /// if the reset reason is implemented but misses dependencies, or if a standard
+    /// reset reason is not implemented.
+    pub fn check(self, support: impl FnOnce(Self) -> ResetSupport) -> Result<Self, Error> {
+        if let Self::Reserved(_) = self {
+            return Err(Error::InvalidParam);
+        }
+        match (support(self), self) {
+            (ResetSupport::Supported, _) => Ok(self),
+            (ResetSupport::Unimplemented, Self::SbiImpl(_) | Self::Vendor(_)) => {
+                Err(Error::InvalidParam)
+            }
+            _ => Err(Error::NotSupported),
+        }
+    }
+}
+
+/// Support of a reset type or reset reason on the current platform.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResetSupport {
+    /// The reset type or reason is implemented and can be used.
+    Supported,
+    /// The reset type or reason is implemented, but the platform misses one or more dependencies.
+    MissingDependencies,
+    /// The reset type or reason is not implemented.
+    Unimplemented,
+}
+
+impl fmt::Display for ResetReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoReason => write!(f, "No reason"),
+            Self::SystemFailure => write!(f, "System failure"),
+            Self::Reserved(raw) => write!(f, "Reserved ({raw:#010x})"),
+            Self::SbiImpl(raw) => write!(f, "SBI implementation specific ({raw:#010x})"),
+            Self::Vendor(raw) => write!(f, "Vendor specific ({raw:#010x})"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ResetReason, ResetSupport, ResetType};
+    use crate::binary::Error;
+
+    #[test]
+    fn reset_type() {
+        let cases = [
+            (0, ResetType::Shutdown),
+            (1, ResetType::ColdReboot),
+            (2, ResetType::WarmReboot),
+            (3, ResetType::Reserved(3)),
+            (0xefff_ffff, ResetType::Reserved(0xefff_ffff)),
+            (0xf000_0000, ResetType::Vendor(0xf000_0000)),
+            (0xffff_ffff, ResetType::Vendor(0xffff_ffff)),
+        ];
+        for (raw, reset_type) in cases {
+            assert_eq!(ResetType::from_raw(raw), reset_type);
+            assert_eq!(reset_type.raw(), raw);
+        }
+        let vendor = ResetType::Vendor(0xf000_0000);
+        assert_eq!(
+            ResetType::Reserved(3).check(|_| unreachable!()),
+            Err(Error::InvalidParam)
+        );
+        assert_eq!(vendor.check(|_| ResetSupport::Supported), Ok(vendor));
+        assert_eq!(
+            vendor.check(|_| ResetSupport::Unimplemented),
+            Err(Error::InvalidParam)
+        );
+        assert_eq!(
+            vendor.check(|_| ResetSupport::MissingDependencies),
+            Err(Error::NotSupported)
+        );
+        assert_eq!(
+            ResetType::ColdReboot.check(|_| ResetSupport::Unimplemented),
+            Err(Error::NotSupported)
+        );
+    }
+
+    #[test]
+    fn reset_reason() {
+        let cases = [
+            (0, ResetReason::NoReason),
+            (1, ResetReason::SystemFailure),
+            (2, ResetReason::Reserved(2)),
+            (0xdfff_ffff, ResetReason::Reserved(0xdfff_ffff)),
+            (0xe000_0000, ResetReason::SbiImpl(0xe000_0000)),
+            (0xefff_ffff, ResetReason::SbiImpl(0xefff_ffff)),
+            (0xf000_0000, ResetReason::Vendor(0xf000_0000)),
+            (0xffff_ffff, ResetReason::Vendor(0xffff_ffff)),
+        ];
+        for (raw, reset_reason) in cases {
+            assert_eq!(ResetReason::from_raw(raw), reset_reason);
+            assert_eq!(reset_reason.raw(), raw);
+        }
+        assert_eq!(
+            ResetReason::Reserved(2).check(|_| unreachable!()),
+            Err(Error::InvalidParam)
+        );
+        assert_eq!(
+            ResetReason::NoReason.check(|_| ResetSupport::Supported),
+            Ok(ResetReason::NoReason)
+        );
+        for reason in [
+            ResetReason::SbiImpl(0xe000_0000),
+            ResetReason::Vendor(0xf000_0001),
+        ] {
+            assert_eq!(reason.check(|_| ResetSupport::Supported), Ok(reason));
+            assert_eq!(
+                reason.check(|_| ResetSupport::Unimplemented),
+                Err(Error::InvalidParam)
+            );
+        }
+    }
+}
@@ -0,0 +1,378 @@
+//! Chapter 17. Supervisor Software Events Extension (EID #0x535345 "SSE").
+
+use crate::binary::Error;
+
+/// Extension ID for Supervisor Software Events Extension.
+pub const EID_SSE: usize = crate::eid_from_str("SSE") as _;
+pub use fid::*;
+
+/// Declared in §17.18.
+mod fid {
+    /// Function ID to read a range of event attribute values from a software event.
+    ///
+    /// Declared in §17.8.
+    pub const READ_ATTRS: usize = 0;
+    /// Function ID to write a range of event attribute values to a software event.
+    ///
+    /// Declared in §17.9.
+    pub const WRITE_ATTRS: usize = 1;
+    /// Function ID to register a software event.
+    ///
+    /// Declared in §17.10.
+    pub const REGISTER: usize = 2;
+    /// Function ID to unregister a software event.
+    ///
+    /// Declared in §17.11.
+    pub const UNREGISTER: usize = 3;
+    /// Function ID to enable a software event.
+    ///
+    /// Declared in §17.12.
+    pub const ENABLE: usize = 4;
+    /// Function ID to disable a software event.
+    ///
+    /// Declared in §17.13.
+    pub const DISABLE: usize = 5;
+    /// Function ID to complete the supervisor event handling.
+    ///
+    /// Declared in §17.14.
+    pub const COMPLETE: usize = 6;
+    /// Function ID to inject a software event.
+    ///
+    /// Declared in §17.15.
+    pub const INJECT: usize = 7;
+    /// Function ID to start receiving software events on the calling hart.
+    ///
+    /// Declared in §17.16.
+    pub const HART_UNMASK: usize = 8;
+    /// Function ID to stop receiving software events on the calling hart.
+    ///
+    /// Declared in §17.17.
+    pub const HART_MASK: usize = 9;
+}
+
+/// Software event IDs.
+///
+/// Bit 15 of the event ID selects a global event, while bit 14 selects a
+/// platform-specific event. Standard event IDs not listed here are reserved.
+///
+/// Declared in §17.1.
+pub mod event_id {
+    /// Local high priority RAS event.
+    pub const LOCAL_HIGH_PRIO_RAS: u32 = 0x0000_0000;
+    /// Local double trap event.
+    pub const LOCAL_DOUBLE_TRAP: u32 = 0x0000_0001;
+    /// Global high priority RAS event.
+    pub const GLOBAL_HIGH_PRIO_RAS: u32 = 0x0000_8000;
+    /// Local PMU overflow event.
+    pub const LOCAL_PMU_OVERFLOW: u32 = 0x0001_0000;
+    /// Local low priority RAS event.
+    pub const LOCAL_LOW_PRIO_RAS: u32 = 0x0010_0000;
+    /// Global low priority RAS event.
+    pub const GLOBAL_LOW_PRIO_RAS: u32 = 0x0010_8000;
+    /// Local software injected event.
+    pub const LOCAL_SOFTWARE_INJECTED: u32 = 0xffff_0000;
+    /// Global software injected event.
+    pub const GLOBAL_SOFTWARE_INJECTED: u32 = 0xffff_8000;
+
+    /// Event ID bit for global events; local events are delivered per hart.
+    pub const GLOBAL: u32 = 1 << 15;
+    /// Event ID bit for platform-specific events.
+    pub const PLATFORM: u32 = 1 << 14;
+}
+
+/// Software event attribute IDs.
+///
+/// Declared in §17.4.
+pub mod attr_id {
+    /// Status of the software event, read-only.
+    pub const STATUS: u32 = 0x0000_0000;
+    /// Priority of the software event.
+    pub const PRIORITY: u32 = 0x0000_0001;
+    /// Configuration of the software event.
+    pub const CONFIG: u32 = 0x0000_0002;
+    /// Hart ID of the preferred hart to handle a global software event.
+    pub const PREFERRED_HART: u32 = 0x0000_0003;
+    /// Entry program counter of the software event handler, read-only.
+    pub const ENTRY_PC: u32 = 0x0000_0004;
+    /// Entry argument of the software event handler, read-only.
+    pub const ENTRY_ARG: u32 = 0x0000_0005;
+    /// Interrupted `sepc` when the software event handler runs.
+    pub const INTERRUPTED_SEPC: u32 = 0x0000_0006;
+    /// Interrupted flags when the software event handler runs.
+    pub const INTERRUPTED_FLAGS: u32 = 0x0000_0007;
+    /// Interrupted `a6` register value when the software event handler runs.
+    pub const INTERRUPTED_A6: u32 = 0x0000_0008;
+    /// Interrupted `a7` register value when the software event handler runs.
+    pub const INTERRUPTED_A7: u32 = 0x0000_0009;
+}
+
+/// Fields of the software event `STATUS` attribute.
+///
+/// Declared in §17.4.
+pub mod status {
+    /// Mask of the software event state, see [`EventState`](super::EventState).
+    pub const STATE: usize = 0b11;
+    /// Software event is pending.
+    pub const PENDING: usize = 1 << 2;
+    /// Software event can be injected.
+    pub const INJECT: usize = 1 << 3;
+}
+
+/// Fields of the software event `CONFIG` attribute.
+///
+/// Declared in §17.4.
+pub mod config {
+    /// Disable the software event automatically after its handler completes.
+    pub const ONESHOT: usize = 1 << 0;
+}
+
+/// Fields of the software event `INTERRUPTED_FLAGS` attribute.
+///
+/// Declared in §17.4.
+pub mod interrupted_flags {
+    /// Interrupted `sstatus.SPP` bit value.
+    pub const SSTATUS_SPP: usize = 1 << 0;
+    /// Interrupted `sstatus.SPIE` bit value.
+    pub const SSTATUS_SPIE: usize = 1 << 1;
+    /// Interrupted `hstatus.SPV` bit value.
+    pub const HSTATUS_SPV: usize = 1 << 2;
+    /// Interrupted `hstatus.SPVP` bit value.
+    pub const HSTATUS_SPVP: usize = 1 << 3;
+    /// Interrupted `sstatus.SPELP` bit value.
+    pub const SSTATUS_SPELP: usize = 1 << 4;
+    /// Interrupted `sstatus.SDT` bit value.
+    pub const SSTATUS_SDT: usize = 1 << 5;
+}
+
+/// Software event ID classified by the event ID space.
+///
+/// Declared in §17.1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EventId {
+    /// Standard software event defined by the SBI specification.
+    Standard(u32),
+    /// Reserved event ID for future use.
+    Reserved(u32),
+    /// Platform-specific event ID.
+    Platform(u32),
+}
+
+impl EventId {
+    /// Classifies a raw software event ID.
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        match raw {
+            event_id::LOCAL_HIGH_PRIO_RAS
+            | event_id::LOCAL_DOUBLE_TRAP
+            | event_id::GLOBAL_HIGH_PRIO_RAS
+            | event_id::LOCAL_PMU_OVERFLOW
+            | event_id::LOCAL_LOW_PRIO_RAS
+            | event_id::GLOBAL_LOW_PRIO_RAS
+            | event_id::LOCAL_SOFTWARE_INJECTED
+            | event_id::GLOBAL_SOFTWARE_INJECTED => return Self::Standard(raw),
+            _ => {}
+        }
+        // Only these groups in bits [31:16] have platform-specific ranges.
+        let has_platform_range = matches!(raw >> 16, 0x0000 | 0x0001 | 0x0010 | 0xffff);
+        if has_platform_range && raw & event_id::PLATFORM != 0 {
+            Self::Platform(raw)
+        } else {
+            Self::Reserved(raw)
+        }
+    }
+
+    /// Returns the raw software event ID.
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        match self {
+            Self::Standard(raw) | Self::Reserved(raw) | Self::Platform(raw) => raw,
+        }
+    }
+
+    /// Returns `true` if this software event is global, or `false` if it is local to a hart.
+    #[inline]
+    pub const fn is_global(self) -> bool {
+        self.raw() & event_id::GLOBAL != 0
+    }
+}
+
+/// State of a software event.
+///
+/// Software event state transitions are driven by SBI calls from supervisor software,
+/// except for `Enabled` to `Running` which happens when the SBI implementation
+/// delivers the event. Functions on this type return the state after a transition,
+/// or [`Error::InvalidState`] if the transition is illegal from the current state.
+///
+/// Declared in §17.2.
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::{binary::Error, sse::EventState};
+/// let state = EventState::Unused.register().unwrap();
+/// let state = state.enable().unwrap();
+/// assert_eq!(state.unregister(), Err(Error::InvalidState));
+/// let state = state.run().unwrap();
+/// assert_eq!(state.complete(false), Ok(EventState::Enabled));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(usize)]
+pub enum EventState {
+    /// Software event is not registered.
+    Unused = 0,
+    /// Software event is registered but not enabled.
+    Registered = 1,
+    /// Software event is enabled and can be delivered.
+    Enabled = 2,
+    /// Software event handler is running.
+    Running = 3,
+}
+
+impl EventState {
+    /// Converts the state field of a raw `STATUS` attribute value into event state.
+    #[inline]
+    pub const fn from_status(status: usize) -> Self {
+        match status & status::STATE {
+            0 => Self::Unused,
+            1 => Self::Registered,
+            2 => Self::Enabled,
+            _ => Self::Running,
+        }
+    }
+
+    /// Returns the raw value of the event state.
+    #[inline]
+    pub const fn raw(self) -> usize {
+        self as usize
+    }
+
+    /// State after the software event register function.
+    #[inline]
+    pub const fn register(self) -> Result<Self, Error> {
+        match self {
+            Self::Unused => Ok(Self::Registered),
+            _ => Err(Error::InvalidState),
+        }
+    }
+
+    /// State after the software event unregister function.
+    #[inline]
+    pub const fn unregister(self) -> Result<Self, Error> {
+        match self {
+            Self::Registered => Ok(Self::Unused),
+            _ => Err(Error::InvalidState),
+        }
+    }
+
+    /// State after the software event enable function.
+    #[inline]
+    pub const fn enable(self) -> Result<Self, Error> {
+        match self {
+            Self::Registered => Ok(Self::Enabled),
+            _ => Err(Error::InvalidState),
+        }
+    }
+
+    /// State after the software event disable function.
+    #[inline]
+    pub const fn disable(self) -> Result<Self, Error> {
+        match self {
+            Self::Enabled => Ok(Self::Registered),
+            _ => Err(Error::InvalidState),
+        }
+    }
+
+    /// State after the SBI implementation delivers the software event to its handler.
+    #[inline]
+    pub const fn run(self) -> Result<Self, Error> {
+        match self {
+            Self::Enabled => Ok(Self::Running),
+            _ => Err(Error::InvalidState),
+        }
+    }
+
+    /// State after the software event complete function.
+    ///
+    /// If the event is configured as `ONESHOT`, it returns to `Registered` state;
+    /// otherwise it returns to `Enabled` state.
+    #[inline]
+    pub const fn complete(self, oneshot: bool) -> Result<Self, Error> {
+        match self {
+            Self::Running if oneshot => Ok(Self::Registered),
+            Self::Running => Ok(Self::Enabled),
+            _ => Err(Error::InvalidState),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventId, EventState};
+    use crate::binary::Error;
+
+    #[test]
+    fn event_id_space() {
+        assert_eq!(
+            EventId::from_raw(0x0000_0001),
+            EventId::Standard(0x0000_0001)
+        );
+        assert_eq!(
+            EventId::from_raw(0x0000_0002),
+            EventId::Reserved(0x0000_0002)
+        );
+        assert_eq!(
+            EventId::from_raw(0x0000_4000),
+            EventId::Platform(0x0000_4000)
+        );
+        assert_eq!(
+            EventId::from_raw(0x0000_c000),
+            EventId::Platform(0x0000_c000)
+        );
+        assert_eq!(
+            EventId::from_raw(0x0001_7fff),
+            EventId::Platform(0x0001_7fff)
+        );
+        assert_eq!(
+            EventId::from_raw(0x0001_8000),
+            EventId::Reserved(0x0001_8000)
+        );
+        assert_eq!(
+            EventId::from_raw(0x0002_4000),
+            EventId::Reserved(0x0002_4000)
+        );
+        assert_eq!(
+            EventId::from_raw(0x0010_c000),
+            EventId::Platform(0x0010_c000)
+        );
+        assert_eq!(
+            EventId::from_raw(0xffff_ffff),
+            EventId::Platform(0xffff_ffff)
+        );
+        assert!(EventId::from_raw(0x0010_8000).is_global());
+        assert!(!EventId::from_raw(0x0001_0000).is_global());
+    }
+
+    #[test]
+    fn event_state_transitions() {
+        use EventState::*;
+        let all = [Unused, Registered, Enabled, Running];
+        for state in all {
+            assert_eq!(EventState::from_status(state.raw()), state);
+            let expected = |legal: EventState, next| {
+                if state == legal {
+                    Ok(next)
+                } else {
+                    Err(Error::InvalidState)
+                }
+            };
+            assert_eq!(state.register(), expected(Unused, Registered));
+            assert_eq!(state.unregister(), expected(Registered, Unused));
+            assert_eq!(state.enable(), expected(Registered, Enabled));
+            assert_eq!(state.disable(), expected(Enabled, Registered));
+            assert_eq!(state.run(), expected(Enabled, Running));
+            assert_eq!(state.complete(false), expected(Running, Enabled));
+            assert_eq!(state.complete(true), expected(Running, Registered));
+        }
+        assert_eq!(EventState::from_status(0b1110), Enabled);
+    }
+}
@@ -1,5 +1,7 @@
 //! Chapter 13. System Suspend Extension (EID #0x53555350 "SUSP").
 
+use crate::binary::Error;
+
 /// Extension ID for System Suspend Extension.
 pub const EID_SUSP: usize = crate::eid_from_str("SUSP") as _;
 pub use fid::*;
@@ -11,3 +13,174 @@ mod fid {
     /// Declared in §13.1.
     pub const SUSPEND: usize = 0;
 }
+
+/// System sleep types.
+///
+/// Sleep types from `0x8000_0000` to `0xFFFF_FFFF` are platform specific; other
+/// values not listed here are reserved.
+///
+/// Declared in §13.1.
+pub mod sleep_type {
+    /// Suspend to RAM, where all harts are stopped and the system memory is retained.
+    pub const SUSPEND_TO_RAM: u32 = 0;
+    /// First platform specific sleep type.
+    pub const PLATFORM_START: u32 = 0x8000_0000;
+}
+
+/// System sleep type classified by the sleep type space.
+///
+/// Declared in §13.1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SleepType {
+    /// Suspend to RAM.
+    SuspendToRam,
+    /// Reserved sleep type for future use.
+    Reserved(u32),
+    /// Platform specific sleep type.
+    Platform(u32),
+}
+
+impl SleepType {
+    /// Classifies a raw sleep type.
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        match raw {
+            sleep_type::SUSPEND_TO_RAM => Self::SuspendToRam,
+            sleep_type::PLATFORM_START.. => Self::Platform(raw),
+            _ => Self::Reserved(raw),
+        }
+    }
+
+    /// Returns the raw sleep type.
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        match self {
+            Self::SuspendToRam => sleep_type::SUSPEND_TO_RAM,
+            Self::Reserved(raw) | Self::Platform(raw) => raw,
+        }
+    }
+}
+
+/// Support of a sleep type on the current platform.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SleepSupport {
+    /// The sleep type is implemented and can be entered.
+    Supported,
+    /// The sleep type is implemented, but the platform misses one or more dependencies.
+    MissingDependencies,
+    /// The sleep type is not implemented.
+    Unimplemented,
+}
+
+/// Parameters of the system suspend function.
+///
+/// The system suspend function only returns on failure. On success, the system enters
+/// the requested sleep state and later resumes from the calling hart, as if it were
+/// started by the hart start function of the HSM extension:
+///
+/// - the hart resumes in supervisor-mode at physical address `resume_addr`;
+/// - register `a0` contains the hart ID and `a1` contains `opaque`;
+/// - `satp` is zero, so that supervisor address translation is disabled;
+/// - `sstatus.SIE` is zero, so that supervisor interrupts are disabled;
+/// - all other registers are in an undefined state.
+///
+/// All harts other than the calling hart must be in the `STOPPED` state before
+/// requesting system suspend, otherwise the request is denied.
+///
+/// Declared in §13.1.
+///
+/// # Examples
+///
+/// ```
+/// # use sbi_spec::{binary::Error, susp::{SleepSupport, SleepType, SuspendRequest}};
+/// let request = SuspendRequest::new(SleepType::from_raw(0x8000_0001), 0x8020_0000, 0);
+/// assert_eq!(request.check(|_| SleepSupport::Unimplemented), Err(Error::InvalidParam));
+/// assert_eq!(request.check(|_| SleepSupport::MissingDependencies), Err(Error::NotSupported));
+/// assert_eq!(request.check(|_| SleepSupport::Supported), Ok(request));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SuspendRequest {
+    /// System sleep type to enter.
+    pub sleep_type: SleepType,
+    /// Physical address where the calling hart resumes in supervisor-mode.
+    pub resume_addr: usize,
+    /// Value passed to register `a1` on resume.
+    pub opaque: usize,
+}
+
+impl SuspendRequest {
+    /// Creates a system suspend request.
+    #[inline]
+    pub const fn new(sleep_type: SleepType, resume_addr: usize, opaque: usize) -> Self {
+        Self {
+            sleep_type,
+            resume_addr,
+            opaque,
+        }
+    }
+
+    /// Converts raw parameters of the system suspend function into a request.
+    #[inline]
+    pub const fn from_raw(sleep_type: u32, resume_addr: usize, opaque: usize) -> Self {
+        Self::new(SleepType::from_raw(sleep_type), resume_addr, opaque)
+    }
+
+    /// Checks the sleep type of this request with its support on the current platform.
+    ///
+    /// `support` is only called for sleep types that are not reserved. Returns
+    /// [`Error::InvalidParam`] if the sleep type is reserved or is platform specific
+    /// and unimplemented, and [`Error::NotSupported`] if the sleep type is implemented
+    /// but misses dependencies, or if suspend to RAM is not implemented.
+    ///
+    /// SBI implementations should still check `resume_addr` and the states of other
+    /// harts before entering the sleep state.
+    pub fn check(self, support: impl FnOnce(SleepType) -> SleepSupport) -> Result<Self, Error> {
+        if let SleepType::Reserved(_) = self.sleep_type {
+            return Err(Error::InvalidParam);
+        }
+        match (support(self.sleep_type), self.sleep_type) {
+            (SleepSupport::Supported, _) => Ok(self),
+            (SleepSupport::Unimplemented, SleepType::Platform(_)) => Err(Error::InvalidParam),
+            _ => Err(Error::NotSupported),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SleepSupport, SleepType, SuspendRequest};
+    use crate::binary::Error;
+
+    #[test]
+    fn sleep_type() {
+        let cases = [
+            (0, SleepType::SuspendToRam),
+            (1, SleepType::Reserved(1)),
+            (0x7fff_ffff, SleepType::Reserved(0x7fff_ffff)),
+            (0x8000_0000, SleepType::Platform(0x8000_0000)),
+            (0xffff_ffff, SleepType::Platform(0xffff_ffff)),
+        ];
+        for (raw, sleep_type) in cases {
+            assert_eq!(SleepType::from_raw(raw), sleep_type);
+            assert_eq!(sleep_type.raw(), raw);
+        }
+    }
+
+    #[test]
+    fn suspend_request() {
+        let request = SuspendRequest::from_raw(1, 0x8020_0000, 0);
+        let ret = request.check(|_| unreachable!());
+        assert_eq!(ret, Err(Error::InvalidParam));
+        let request = SuspendRequest::from_raw(0, 0x8020_0000, 0x55);
+        assert_eq!(request.sleep_type, SleepType::SuspendToRam);
+        assert_eq!(request.check(|_| SleepSupport::Supported), Ok(request));
+        assert_eq!(
+            request.check(|_| SleepSupport::MissingDependencies),
+            Err(Error::NotSupported)
+        );
+        assert_eq!(
+            request.check(|_| SleepSupport::Unimplemented),
+            Err(Error::NotSupported)
+        );
+    }
+}